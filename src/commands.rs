@@ -1,6 +1,23 @@
+pub mod amend;
+pub mod delete;
 pub mod edit;
+pub mod fsck;
+pub mod log;
+pub mod man;
+pub mod merge;
+pub mod pause;
+pub mod ping;
+pub mod project;
+pub mod projects;
 pub mod register;
 pub mod report;
+pub mod search;
+pub mod show;
+pub mod split;
 pub mod start;
 pub mod status;
 pub mod stop;
+pub mod switch;
+pub mod tag;
+pub mod tags;
+pub mod unpause;
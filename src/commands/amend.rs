@@ -0,0 +1,101 @@
+use crate::event::{find_event, find_last_event, validate_project};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::to_naive_date_time;
+use anyhow::anyhow;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    Command::new("amend")
+        .about("Change an event, without opening an editor")
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time to change, instead of the last event")
+                .long("id"),
+        )
+        .arg(
+            Arg::new("project")
+                .help("New project name")
+                .short('p')
+                .long("project"),
+        )
+        .arg(
+            Arg::new("description")
+                .help("New description")
+                .short('d')
+                .long("description"),
+        )
+        .arg(
+            Arg::new("add_tag")
+                .help("Tag(s) to add")
+                .short('t')
+                .long("add-tag"),
+        )
+        .arg(
+            Arg::new("remove_tag")
+                .help("Tag(s) to remove")
+                .long("remove-tag"),
+        )
+        .arg(
+            Arg::new("start_time")
+                .help("New start time (e.g., '08:00')")
+                .long("start"),
+        )
+        .arg(
+            Arg::new("stop_time")
+                .help("New stop time (e.g., '17:00')")
+                .long("stop"),
+        )
+        .arg(crate::quiet_arg())
+}
+
+fn split_tags(args: &clap::ArgMatches, id: &str) -> Vec<String> {
+    match args.get_one::<String>(id) {
+        Some(tag) => tag.split(',').map(|s| s.trim().to_string()).collect(),
+        None => vec![],
+    }
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut event = match args.get_one::<String>("id") {
+        Some(id) => find_event(id)?,
+        None => find_last_event()?,
+    };
+
+    let old_path = event.source_path();
+
+    if let Some(project) = args.get_one::<String>("project") {
+        validate_project(project)?;
+        event.project = project.clone();
+    }
+
+    if let Some(description) = args.get_one::<String>("description") {
+        event.description = description.clone();
+    }
+
+    event.remove_tags(&split_tags(args, "remove_tag"));
+    event.add_tags(split_tags(args, "add_tag"));
+
+    if let Some(start) = args.get_one::<String>("start_time") {
+        event.start = to_naive_date_time(Some(start), Some(&event.start))?;
+    }
+
+    if let Some(stop) = args.get_one::<String>("stop_time") {
+        event.stop = Some(to_naive_date_time(Some(stop), Some(&event.start))?);
+    }
+
+    if let Some(stop) = event.stop
+        && stop < event.start
+    {
+        return Err(anyhow!("The stop time cannot be before the start time"));
+    }
+
+    event.move_from(&old_path)?;
+    hooks::run_moved(&event, &old_path);
+
+    if !args.get_flag("quiet") {
+        print_table(event.to_table("Amended"), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
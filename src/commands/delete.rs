@@ -0,0 +1,67 @@
+use crate::event::{TimeEvent, find_event, find_events};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::to_naive_date_time;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    Command::new("delete")
+        .about("Delete event(s)")
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time, as listed by 'tt log'")
+                .num_args(1..)
+                .index(1),
+        )
+        .arg(
+            Arg::new("since")
+                .help("From what start time, instead of event ids")
+                .long("since")
+                .required_unless_present("id")
+                .conflicts_with("id"),
+        )
+        .arg(
+            Arg::new("until")
+                .help("Until what start time, instead of event ids")
+                .long("until")
+                .required_unless_present("id")
+                .conflicts_with("id"),
+        )
+        .arg(crate::filter_arg().conflicts_with("id"))
+        .arg(crate::dry_run_arg())
+        .arg(crate::quiet_arg())
+}
+
+fn find_delete_events(args: &clap::ArgMatches) -> Result<Vec<TimeEvent>, anyhow::Error> {
+    if let Some(ids) = args.get_many::<String>("id") {
+        return ids.map(|id| find_event(id)).collect();
+    }
+
+    let since = to_naive_date_time(args.get_one::<String>("since"), None)?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+    Ok(find_events(&since.date(), &until.date())
+        .into_iter()
+        .filter(|e| e.start >= since && e.start <= until && e.matches_args(args))
+        .collect())
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    // Find all events before deleting any of them, in case one of the ids is invalid
+    let events = find_delete_events(args)?;
+
+    for event in events {
+        let status = if args.get_flag("dry_run") {
+            "Would delete"
+        } else {
+            event.delete()?;
+            hooks::run(&["on-delete"], &event);
+            "Deleted"
+        };
+
+        if !args.get_flag("quiet") {
+            print_table(event.to_table(status), plain_table(), [1, 1]);
+        }
+    }
+
+    Ok(0)
+}
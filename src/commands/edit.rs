@@ -1,7 +1,12 @@
-use crate::event::{TimeEvent, find_events, find_last_event};
-use crate::utils::to_naive_date_time;
+use crate::event::{TimeEvent, find_event, find_events, find_last_event, validate_project};
+use crate::hooks;
+use crate::styling::{print_table, regular_table};
+use crate::utils::{format_date, to_naive_date_time};
+use anyhow::anyhow;
 use clap::{Arg, ArgAction, Command};
+use prettytable::{Table, row};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
 pub fn command() -> Command {
@@ -11,6 +16,11 @@ pub fn command() -> Command {
 
     Command::new("edit")
         .about("Edit event(s)")
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time to edit, instead of --since and --until")
+                .index(1),
+        )
         .arg(
             Arg::new("since")
                 .help("From what start time for event(s) to edit")
@@ -24,12 +34,274 @@ pub fn command() -> Command {
                 .default_value(example_until.to_string()),
         )
         .arg(
-            Arg::new("dry_run")
-                .help("Only show what would be done")
-                .long("dry-run")
+            Arg::new("batch")
+                .help("Edit all the events in a single editor buffer")
+                .short('b')
+                .long("batch")
                 .num_args(0)
                 .action(ArgAction::SetTrue),
         )
+        .arg(
+            Arg::new("format")
+                .help("Edit the event(s) as JSON or as one line per event")
+                .long("format")
+                .value_parser(["json", "line"])
+                .default_value("json"),
+        )
+        .arg(crate::filter_arg())
+        .arg(crate::dry_run_arg())
+}
+
+fn editor_argv(editor: &str) -> Result<Vec<&str>, anyhow::Error> {
+    let argv = editor.split_whitespace().collect::<Vec<&str>>();
+    if argv.is_empty() {
+        return Err(anyhow!("EDITOR is empty"));
+    }
+
+    Ok(argv)
+}
+
+fn open_editor(editor: &str, tmp: &NamedTempFile) -> Result<String, anyhow::Error> {
+    let mut argv = editor_argv(editor)?;
+    println!("$ {} \"{}\"", editor, tmp.path().to_string_lossy());
+
+    let _ = std::process::Command::new(argv.remove(0))
+        .args(argv)
+        .arg(tmp.path())
+        .status();
+
+    Ok(std::fs::read_to_string(tmp.path())?)
+}
+
+fn strip_comments(content: &str) -> String {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+/// Opens the editor until `parse()` accepts the content, or the user gives up
+/// by saving an empty or unchanged buffer
+fn edit_until_valid<T>(
+    editor: &str,
+    content: String,
+    parse: impl Fn(&str) -> Result<T, anyhow::Error>,
+) -> Result<Option<T>, anyhow::Error> {
+    let mut content = content;
+    let mut previous: Option<String> = None;
+
+    loop {
+        let mut tmp = NamedTempFile::new()?;
+        write!(tmp, "{content}")?;
+
+        let edited = strip_comments(&open_editor(editor, &tmp)?);
+        if edited.trim().is_empty() || previous.as_ref() == Some(&edited) {
+            eprintln!("Edit aborted, nothing was changed");
+            return Ok(None);
+        }
+
+        match parse(&edited) {
+            Ok(parsed) => return Ok(Some(parsed)),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                content = format!(
+                    "# Error: {e}\n# Fix the error, or save an empty or unchanged file to abort.\n{edited}"
+                );
+                previous = Some(edited);
+            }
+        }
+    }
+}
+
+fn validate(event: TimeEvent) -> Result<TimeEvent, anyhow::Error> {
+    validate_project(&event.project)?;
+
+    if let Some(stop) = event.stop
+        && stop < event.start
+    {
+        return Err(anyhow!("The stop time cannot be before the start time"));
+    }
+
+    Ok(event)
+}
+
+// Checks that the edited event can be moved, before any file is changed
+fn check_move(edited: &TimeEvent, source: &Path) -> Result<(), anyhow::Error> {
+    let path = edited.path();
+    if path != source && path.exists() {
+        return Err(anyhow!("{path:?} already exists"));
+    }
+
+    Ok(())
+}
+
+const LINE_FORMAT_HELP: &str = "\
+# Format: id  YYYY-MM-DD HH:MM - HH:MM  project  +tag1 +tag2  description
+# The stop time can be \"?\" for a running event, or include a date.
+# The description can be a quoted JSON string, such as \"+1 on\\nthis\".
+# In batch mode, lines without an id are created and removed lines are deleted.
+";
+
+// Each event gets an "id", so the edited events can be matched with the original events
+fn serialize_batch(events: &[TimeEvent], format: &str) -> Result<String, anyhow::Error> {
+    if format == "line" {
+        let lines = events.iter().map(|e| e.to_line()).collect::<Vec<String>>();
+        return Ok(format!("{LINE_FORMAT_HELP}{}\n", lines.join("\n")));
+    }
+
+    let mut json = vec![];
+    for event in events {
+        let mut value = event.to_json()?;
+        value["id"] = event.id().into();
+        json.push(value);
+    }
+
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+fn parse_batch(
+    content: &str,
+    format: &str,
+    originals: &[TimeEvent],
+) -> Result<Vec<(Option<String>, TimeEvent)>, anyhow::Error> {
+    let mut events = vec![];
+    if format == "line" {
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            // An unchanged line is not parsed, since the line does not include every field
+            if let Some(original) = originals.iter().find(|o| o.to_line() == line.trim()) {
+                events.push((Some(original.id()), original.clone()));
+                continue;
+            }
+
+            let (id, event) = TimeEvent::from_line(line, originals)?;
+            events.push((id, validate(event)?));
+        }
+
+        return Ok(events);
+    }
+
+    for mut value in serde_json::from_str::<Vec<serde_json::Value>>(content)? {
+        let id = value
+            .as_object_mut()
+            .and_then(|obj| obj.remove("id"))
+            .and_then(|id| id.as_str().map(|s| s.to_string()));
+
+        events.push((id, validate(TimeEvent::from_json(value)?)?));
+    }
+
+    Ok(events)
+}
+
+// Checks the edited events before any file is changed, so a conflict reopens the editor,
+// instead of leaving the tracker directory half way updated
+fn check_batch(
+    edited: &[(Option<String>, TimeEvent)],
+    originals: &[TimeEvent],
+) -> Result<(), anyhow::Error> {
+    let ids = edited
+        .iter()
+        .filter_map(|(id, _)| id.as_ref())
+        .collect::<Vec<&String>>();
+
+    // Only the files of deleted events are free, since the deletes are done first
+    let deleted = originals
+        .iter()
+        .filter(|o| !ids.contains(&&o.id()))
+        .map(|o| o.source_path())
+        .collect::<Vec<PathBuf>>();
+
+    let mut seen = vec![];
+    let mut paths = vec![];
+    for (id, event) in edited {
+        if let Some(id) = id {
+            if seen.contains(&id) {
+                return Err(anyhow!("The id {id} is used more than once"));
+            }
+
+            seen.push(id);
+        }
+
+        let path = event.path();
+        if paths.contains(&path) {
+            return Err(anyhow!("More than one event would be saved to {path:?}"));
+        }
+
+        let source = originals
+            .iter()
+            .find(|o| Some(&o.id()) == id.as_ref())
+            .map(|o| o.source_path());
+        if path.exists() && source.as_ref() != Some(&path) && !deleted.contains(&path) {
+            return Err(anyhow!("{path:?} already exists"));
+        }
+
+        paths.push(path);
+    }
+
+    Ok(())
+}
+
+fn run_batch(editor: &str, format: &str, originals: Vec<TimeEvent>) -> Result<i32, anyhow::Error> {
+    let content = serialize_batch(&originals, format)?;
+    let parse = |content: &str| {
+        let edited = parse_batch(content, format, &originals)?;
+        check_batch(&edited, &originals)?;
+        Ok(edited)
+    };
+    let Some(edited) = edit_until_valid(editor, content, parse)? else {
+        return Ok(1);
+    };
+
+    let mut report = Table::new();
+    report.set_titles(row!["Action", "Date", "Project", "Start", "File"]);
+    let mut add_row = |action: &str, event: &TimeEvent| {
+        report.add_row(row![
+            action,
+            format_date(&event.start, "ymd"),
+            event.project,
+            format_date(&event.start, "hm"),
+            event.path().to_string_lossy(),
+        ]);
+    };
+
+    // Deletes must be done first, in case a new or moved event takes its place
+    for original in &originals {
+        let id = original.id();
+        if !edited
+            .iter()
+            .any(|(edited_id, _)| edited_id.as_ref() == Some(&id))
+        {
+            original.delete()?;
+            hooks::run(&["on-delete"], original);
+            add_row("Deleted", original);
+        }
+    }
+
+    for (id, event) in &edited {
+        let original = originals.iter().find(|o| Some(&o.id()) == id.as_ref());
+        match original {
+            Some(original) if original.serialize(false)? == event.serialize(false)? => {}
+            Some(original) if original.source_path() != event.path() => {
+                event.move_from(&original.source_path())?;
+                hooks::run_moved(event, &original.source_path());
+                add_row("Moved", event);
+            }
+            Some(_) => {
+                event.save()?;
+                hooks::run(&["on-save"], event);
+                add_row("Updated", event);
+            }
+            None => {
+                event.save()?;
+                hooks::run(&["on-save"], event);
+                add_row("Created", event);
+            }
+        }
+    }
+
+    print_table(report, regular_table(), [1, 1]);
+
+    Ok(0)
 }
 
 pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
@@ -37,37 +309,63 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
     let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
     let editor = std::env::var("EDITOR").unwrap_or_else(|_| "vi".to_string());
 
-    for event in find_events(&since.date(), &until.date()) {
-        if event.start < since || event.start > until {
-            continue;
-        }
+    let events = match args.get_one::<String>("id") {
+        Some(id) => vec![find_event(id)?],
+        None => find_events(&since.date(), &until.date())
+            .into_iter()
+            .filter(|e| e.start >= since && e.start <= until && e.matches_args(args))
+            .collect(),
+    };
 
-        if args.get_flag("dry_run") {
+    if args.get_flag("dry_run") {
+        for event in events {
             println!("{} {}", editor, event.path().to_string_lossy());
-            continue;
         }
 
-        let mut editor_argv = editor.split_whitespace().collect::<Vec<&str>>();
-        if editor_argv.is_empty() {
-            continue;
-        }
+        return Ok(0);
+    }
 
-        let mut tmp = NamedTempFile::new()?;
-        write!(tmp, "{}", event.serialize(true)?)?;
-        println!("$ {} \"{}\"", editor, tmp.path().to_string_lossy());
-
-        let _ = std::process::Command::new(editor_argv.remove(0))
-            .args(editor_argv)
-            .arg(tmp.path())
-            .status();
-
-        let content = std::fs::read_to_string(tmp.path())?;
-        if TimeEvent::from_string(&content).is_ok() {
-            println!("$ mv {:?} {:?}", tmp.path(), event.path());
-            tmp.persist(event.path())?;
+    let format = args.get_one::<String>("format").expect("Default missing");
+    if args.get_flag("batch") {
+        return run_batch(&editor, format, events);
+    }
+
+    editor_argv(&editor)?;
+    for event in events {
+        let originals = std::slice::from_ref(&event);
+        let parse = |content: &str| {
+            let edited = if format == "line" {
+                let mut edited = parse_batch(content, format, originals)?;
+                match (edited.pop(), edited.is_empty()) {
+                    (Some((_, event)), true) => event,
+                    _ => return Err(anyhow!("Expected a single line")),
+                }
+            } else {
+                validate(TimeEvent::from_json(serde_json::from_str(content)?)?)?
+            };
+
+            check_move(&edited, &event.source_path())?;
+            Ok(edited)
+        };
+
+        let content = if format == "line" {
+            serialize_batch(originals, format)?
         } else {
-            eprintln!("Unable to move {:?} to {:?}", tmp.path(), event.path());
+            event.serialize(true)?
+        };
+
+        let edited = edit_until_valid(&editor, content, parse)?;
+        let Some(edited) = edited else {
+            continue;
+        };
+
+        if edited.path() != event.source_path() {
+            println!("$ mv {:?} {:?}", event.source_path(), edited.path());
         }
+
+        // The file must be moved if the start time or project was changed
+        edited.move_from(&event.source_path())?;
+        hooks::run_moved(&edited, &event.source_path());
     }
 
     Ok(0)
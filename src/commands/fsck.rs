@@ -0,0 +1,121 @@
+use crate::event::{FileProblem, TimeEvent, check_file, find_files};
+use crate::hooks;
+use crate::styling::{DASH, plain_table, print_table, regular_table};
+use clap::{Arg, ArgAction, Command};
+use prettytable::{Table, row};
+use std::path::PathBuf;
+
+pub fn command() -> Command {
+    Command::new("fsck")
+        .about("Validate and repair the tracker directory")
+        .arg(
+            Arg::new("fix")
+                .help("Repair the problems that can be repaired")
+                .long("fix")
+                .num_args(0)
+                .action(ArgAction::SetTrue),
+        )
+}
+
+fn is_fixable(problem: &FileProblem) -> bool {
+    matches!(
+        problem,
+        FileProblem::InvalidStart(_)
+            | FileProblem::StartMismatch
+            | FileProblem::ProjectMismatch
+            | FileProblem::StaleDuration
+    )
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let fix = args.get_flag("fix");
+    let mut report = Table::new();
+    report.set_titles(row!["File", "Problem", "Action"]);
+
+    let mut total_problems = 0;
+    let mut total_fixed = 0;
+    let mut events: Vec<(PathBuf, TimeEvent)> = vec![];
+
+    for path in find_files() {
+        let (event, problems) = check_file(&path);
+        total_problems += problems.len();
+
+        let Some(event) = event else {
+            for problem in problems {
+                report.add_row(row![path.to_string_lossy(), problem, DASH]);
+            }
+            continue;
+        };
+
+        let can_fix = fix && !problems.is_empty() && problems.iter().all(is_fixable);
+        let action = if !can_fix {
+            DASH.to_string()
+        } else if let Err(e) = event.move_from(&path) {
+            e.to_string()
+        } else {
+            total_fixed += problems.len();
+            hooks::run_moved(&event, &path);
+            format!("Saved {}", event.path().to_string_lossy())
+        };
+
+        for problem in problems {
+            report.add_row(row![path.to_string_lossy(), problem, action]);
+        }
+
+        let path = if action.starts_with("Saved") {
+            event.path()
+        } else {
+            path
+        };
+
+        events.push((path, event));
+    }
+
+    // Only the last event of each project is allowed to be running
+    events.sort_by_key(|(_, e)| e.start);
+    for i in 0..events.len() {
+        let project = events[i].1.project.clone();
+        let later = || events[i + 1..].iter().filter(|(_, e)| e.project == project);
+        if events[i].1.stop.is_some() || !later().any(|(_, e)| e.stop.is_none()) {
+            continue;
+        }
+
+        total_problems += 1;
+        let next_start = later().next().map(|(_, e)| e.start);
+        let (path, event) = &mut events[i];
+        let action = if let Some(next_start) = next_start
+            && fix
+        {
+            event.stop = Some(next_start);
+            // The file that was read can differ from path(), such as for a .json file
+            event.move_from(path)?;
+            hooks::run(&["on-stop"], event);
+            hooks::run_moved(event, path);
+            total_fixed += 1;
+            format!("Stopped at {}", next_start.format("%Y-%m-%d %H:%M"))
+        } else {
+            DASH.to_string()
+        };
+
+        report.add_row(row![
+            path.to_string_lossy(),
+            "Multiple running events",
+            action
+        ]);
+    }
+
+    if total_problems > 0 {
+        print_table(report, regular_table(), [1, 1]);
+    }
+
+    let mut summary = Table::new();
+    summary.add_row(row!["Problems found:", total_problems.to_string()]);
+    summary.add_row(row!["Problems fixed:", total_fixed.to_string()]);
+    print_table(
+        summary,
+        plain_table(),
+        [usize::from(total_problems == 0), 1],
+    );
+
+    Ok(if total_problems > total_fixed { 1 } else { 0 })
+}
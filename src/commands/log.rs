@@ -0,0 +1,97 @@
+use crate::event::{TimeEvent, find_events};
+use crate::styling::{DASH, print_table, regular_table};
+use crate::utils::{format_date, format_duration, to_naive_date_time};
+use chrono::Datelike;
+use clap::{Arg, Command};
+use prettytable::{Cell, Row, Table, row};
+
+pub fn command() -> Command {
+    let now = chrono::Local::now();
+    let first_of_month = now.with_day(1).expect("Invalid day");
+
+    Command::new("log")
+        .about("List events with their id")
+        .arg(
+            Arg::new("project")
+                .help("Event project name")
+                .short('p')
+                .long("project"),
+        )
+        .arg(
+            Arg::new("tag")
+                .help("Event tag(s) to filter by")
+                .short('t')
+                .long("tag"),
+        )
+        .arg(crate::filter_arg())
+        .arg(
+            Arg::new("since")
+                .help("From what time")
+                .long("since")
+                .default_value(first_of_month.format("%Y-%m-%d").to_string()),
+        )
+        .arg(
+            Arg::new("until")
+                .help("Until what time")
+                .long("until")
+                .default_value(format_date(&now.naive_local(), "hm")),
+        )
+        .arg(crate::json_arg())
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let since = to_naive_date_time(args.get_one::<String>("since"), None)?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+
+    let events = find_events(&since.date(), &until.date())
+        .into_iter()
+        .filter(|e| e.start >= since && e.start <= until && e.matches_args(args))
+        .collect::<Vec<TimeEvent>>();
+
+    if args.get_flag("json") {
+        let mut json = vec![];
+        for event in &events {
+            let mut value = event.to_json()?;
+            value["id"] = event.id().into();
+            json.push(value);
+        }
+
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(0);
+    }
+
+    let mut report = Table::new();
+    report.set_titles(row![
+        "Id",
+        "Date",
+        "Project",
+        "Start",
+        "Stop",
+        "Duration",
+        "Tags",
+        "Description"
+    ]);
+
+    for event in &events {
+        let stop = if let Some(d) = event.stop {
+            format_date(&d, "hm")
+        } else {
+            DASH.to_string()
+        };
+
+        report.add_row(Row::new(vec![
+            Cell::new(&event.id()),
+            Cell::new(&format_date(&event.start, "ymd")),
+            Cell::new(&event.project),
+            Cell::new(&format_date(&event.start, "hm")),
+            Cell::new(&stop),
+            Cell::new(&format_duration(&event.duration())).style_spec("r"),
+            Cell::new(&event.tags_as_string()),
+            Cell::new(event.description()),
+        ]));
+    }
+
+    print_table(report, regular_table(), [1, 1]);
+
+    Ok(0)
+}
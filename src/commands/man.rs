@@ -0,0 +1,149 @@
+use anyhow::anyhow;
+use clap::{Arg, Command};
+use clap_mangen::Man;
+use std::io::Write;
+
+const ENVIRONMENT: &str = r#".SH ENVIRONMENT
+.TP
+\fBEDITOR\fR
+The editor used by \fBtt edit\fR. Default is "vi".
+.TP
+\fBHOME\fR
+Used to find the tracker directory \fI~/.TimeTracker\fR.
+.TP
+\fBTT_HOOK_TIMEOUT\fR
+Hooks running for longer than this number of seconds are killed. Default is 10.
+.TP
+\fBTT_IDLE_DURATION\fR
+\fBtt ping\fR stops the running event at the last activity, after this number of seconds without activity. Default is 900.
+.TP
+\fBTT_MAX_DURATION\fR
+\fBtt stop\fR prints a warning if the event lasted longer than this number of seconds. Default is 36000.
+.TP
+\fBTT_MIN_DURATION\fR
+Events shorter than this number of seconds are discarded when stopped. Default is 300.
+.TP
+\fBCOMPLETE\fR
+Set to "bash", "zsh" or "fish" to print a shell completion script.
+"#;
+
+const FILES: &str = r#".SH FILES
+.TP
+\fI~/.TimeTracker/YYYY/MM/YYYYMMDD\-HHMMSS_project.trc\fR
+Each event is stored as a JSON object in its own file, named after the start time and the project. The file format is shared with App::TimeTracker:
+.PP
+.RS
+.nf
+{
+  "__CLASS__": "App::TimeTracker::Data::Task",
+  "project": "app\-tt",
+  "start": "2025\-09\-07T20:52:48",
+  "stop": "2025\-09\-07T21:12:48",
+  "duration": "00:20:00",
+  "seconds": 1200,
+  "tags": ["rust"],
+  "description": "Convert from perl to rust",
+  "user": "batman"
+}
+.fi
+.RE
+.PP
+The "stop" field is missing for a running event. The "duration" and "seconds" fields are only kept for App::TimeTracker, and can be repaired with \fBtt fsck \-\-fix\fR. Unknown fields are preserved when a file is rewritten.
+.TP
+\fI~/.config/tt/config.toml\fR
+Maps directory patterns to project names, and holds the settings for each project:
+.PP
+.RS
+.nf
+[[directory]]
+path = "~/clients/acme/*"
+project = "acme"
+
+[project.acme]
+branch_tag = true
+issue_keys = "tag"
+issue_pattern = "[A\-Z][A\-Z0\-9]+\-[0\-9]+"
+.fi
+.RE
+.TP
+\fI~/.config/tt/hooks/on\-start\fR, \fIon\-stop\fR, \fIon\-save\fR, \fIon\-delete\fR
+Executables that are run after an event is started, stopped, saved or deleted. The event is written as JSON to STDIN, and is available in the environment variables TT_HOOK, TT_ID, TT_PROJECT, TT_START, TT_STOP, TT_DURATION, TT_TAGS, TT_DESCRIPTION and TT_FILE.
+.TP
+\fI.tt\-project\fR, \fI.tt.toml\fR
+The project for the directory and its sub directories. \fI.tt\-project\fR contains the project name, and \fI.tt.toml\fR has a "project" key and the same settings as "[project.name]" above.
+.TP
+\fI~/.TimeTracker/.last\-activity\fR
+The time of the last activity recorded by \fBtt ping\fR.
+"#;
+
+pub fn command() -> Command {
+    Command::new("man")
+        .about("Print or install man pages")
+        .arg(
+            Arg::new("name")
+                .help("The sub command to print the man page for, such as 'start'")
+                .num_args(0..)
+                .index(1),
+        )
+        .arg(
+            Arg::new("output_dir")
+                .help("Write all the man pages to this directory")
+                .short('o')
+                .long("output-dir"),
+        )
+}
+
+fn render(cmd: Command, w: &mut dyn Write) -> Result<(), anyhow::Error> {
+    let is_root = cmd.get_name() == "tt";
+    let mut buf = vec![];
+    Man::new(cmd).render(&mut buf)?;
+
+    let mut page = String::from_utf8(buf)?;
+    if is_root && let Some(pos) = page.find(".SH VERSION") {
+        page.insert_str(pos, &format!("{ENVIRONMENT}{FILES}"));
+    }
+
+    Ok(w.write_all(page.as_bytes())?)
+}
+
+fn commands(cmd: Command) -> Vec<Command> {
+    let mut all = vec![];
+    for sub in cmd.get_subcommands() {
+        all.extend(commands(sub.clone()));
+    }
+
+    all.insert(0, cmd);
+    all
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut root = crate::cli().disable_help_subcommand(true);
+    root.build();
+
+    if let Some(dir) = args.get_one::<String>("output_dir") {
+        std::fs::create_dir_all(dir)?;
+        for cmd in commands(root) {
+            let path = std::path::Path::new(dir).join(Man::new(cmd.clone()).get_filename());
+            let mut file = std::fs::File::create(&path)?;
+            render(cmd, &mut file)?;
+            println!("Wrote {}", path.to_string_lossy());
+        }
+
+        return Ok(0);
+    }
+
+    let mut display_name = vec!["tt"];
+    if let Some(name) = args.get_many::<String>("name") {
+        display_name.extend(name.map(|s| s.as_str()));
+    }
+
+    let display_name = display_name.join("-");
+
+    let cmd = commands(root)
+        .into_iter()
+        .find(|c| c.get_display_name().unwrap_or(c.get_name()) == display_name)
+        .ok_or_else(|| anyhow!("Unknown command \"{display_name}\""))?;
+
+    render(cmd, &mut std::io::stdout())?;
+    Ok(0)
+}
@@ -0,0 +1,181 @@
+use crate::event::{Break, TimeEvent, find_event, find_events};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::to_naive_date_time;
+use anyhow::anyhow;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    Command::new("merge")
+        .about("Merge events into a single event")
+        .arg(
+            Arg::new("id")
+                .help("Event ids or start times, as listed by 'tt log'")
+                .num_args(1..)
+                .index(1),
+        )
+        .arg(
+            Arg::new("project")
+                .help("Only merge events with this project name")
+                .short('p')
+                .long("project")
+                .conflicts_with("id"),
+        )
+        .arg(
+            Arg::new("since")
+                .help("From what start time, instead of event ids")
+                .long("since")
+                .required_unless_present("id")
+                .conflicts_with("id"),
+        )
+        .arg(
+            Arg::new("until")
+                .help("Until what start time, instead of event ids")
+                .long("until")
+                .required_unless_present("id")
+                .conflicts_with("id"),
+        )
+        .arg(crate::dry_run_arg())
+        .arg(crate::quiet_arg())
+}
+
+fn find_merge_events(args: &clap::ArgMatches) -> Result<Vec<TimeEvent>, anyhow::Error> {
+    if let Some(ids) = args.get_many::<String>("id") {
+        return ids.map(|id| find_event(id)).collect();
+    }
+
+    let since = to_naive_date_time(args.get_one::<String>("since"), None)?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+    Ok(find_events(&since.date(), &until.date())
+        .into_iter()
+        .filter(|e| e.start >= since && e.start <= until && e.matches_args(args))
+        .collect())
+}
+
+/// Combines the events into one event that spans from the earliest start to
+/// the latest stop. The time between the events is recorded as breaks, so the
+/// duration is unchanged. The merged event is running if any of the events are.
+fn merge(events: &[TimeEvent]) -> Result<TimeEvent, anyhow::Error> {
+    let mut merged = events[0].clone();
+    let mut descriptions: Vec<&str> = vec![];
+    merged.breaks = events.iter().flat_map(|e| e.breaks.clone()).collect();
+
+    for event in events {
+        if event.project != merged.project {
+            return Err(anyhow!(
+                "Cannot merge events for both \"{}\" and \"{}\"",
+                merged.project,
+                event.project
+            ));
+        }
+
+        if let Some(stop) = merged.stop
+            && stop < event.start
+        {
+            merged.breaks.push(Break {
+                start: stop,
+                stop: Some(event.start),
+            });
+        }
+
+        merged.stop = match (merged.stop, event.stop) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            _ => None,
+        };
+
+        merged.add_tags(event.tags.clone());
+        if !event.description.is_empty() && !descriptions.contains(&event.description.as_str()) {
+            descriptions.push(&event.description);
+        }
+    }
+
+    merged.breaks.sort_by_key(|b| b.start);
+    merged
+        .breaks
+        .dedup_by(|a, b| a.start == b.start && a.stop == b.stop);
+    merged.description = descriptions.join("; ");
+    Ok(merged)
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut events = find_merge_events(args)?;
+    events.sort_by_key(|e| e.start);
+    events.dedup_by_key(|e| e.source_path());
+
+    if events.len() < 2 {
+        return Err(anyhow!("At least two events are required to merge"));
+    }
+
+    // The merged event has the same start and project as the first event, so
+    // it replaces the first file and the rest of the files are deleted
+    let merged = merge(&events)?;
+    let status = if args.get_flag("dry_run") {
+        "Would merge"
+    } else {
+        merged.save()?;
+        hooks::run(&["on-save"], &merged);
+        for event in &events[1..] {
+            event.delete()?;
+            hooks::run(&["on-delete"], event);
+        }
+        "Merged"
+    };
+
+    if !args.get_flag("quiet") {
+        print_table(merged.to_table(status), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(project: &str, start: &str, stop: Option<&str>, description: &str) -> TimeEvent {
+        let parse = |s: &str| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        TimeEvent {
+            project: project.to_string(),
+            start: parse(start),
+            stop: stop.map(parse),
+            tags: vec![project.to_string()],
+            description: description.to_string(),
+            ..TimeEvent::default()
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        let events = vec![
+            event("work", "2025-09-08 08:00", Some("2025-09-08 09:00"), "a"),
+            event("work", "2025-09-08 09:00", Some("2025-09-08 10:00"), ""),
+            event("work", "2025-09-08 14:00", Some("2025-09-08 15:00"), "b"),
+        ];
+
+        let merged = merge(&events).unwrap();
+        assert_eq!(merged.start, events[0].start);
+        assert_eq!(merged.stop, events[2].stop);
+        assert_eq!(merged.duration().num_hours(), 3);
+        assert_eq!(merged.tags, vec!["work"]);
+        assert_eq!(merged.description, "a; b");
+
+        // Only the gap between the events is a break
+        assert_eq!(merged.breaks.len(), 1);
+        assert_eq!(merged.breaks[0].start, events[1].stop.unwrap());
+        assert_eq!(merged.breaks[0].stop, Some(events[2].start));
+
+        let merged = merge(&[
+            event("work", "2025-09-08 08:00", None, ""),
+            event("work", "2025-09-08 14:00", Some("2025-09-08 15:00"), ""),
+        ])
+        .unwrap();
+        assert_eq!(merged.stop, None);
+        assert!(merged.breaks.is_empty());
+
+        let err = merge(&[
+            event("work", "2025-09-08 08:00", Some("2025-09-08 09:00"), ""),
+            event("oncall", "2025-09-08 09:00", Some("2025-09-08 10:00"), ""),
+        ]);
+        assert!(err.is_err());
+    }
+}
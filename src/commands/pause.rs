@@ -0,0 +1,54 @@
+use crate::event::{find_last_event, find_running_events};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::{format_date, to_time_arg};
+use anyhow::anyhow;
+use clap::{Arg, Command};
+
+pub fn pause_command(name: &'static str, about: &'static str) -> Command {
+    let now = chrono::Local::now().naive_local();
+
+    Command::new(name)
+        .about(about)
+        .arg(
+            Arg::new("time")
+                .help("The time of the break (e.g., '12:00')")
+                .default_value(format_date(&now, "hm"))
+                .index(1),
+        )
+        .arg(
+            Arg::new("project")
+                .help("Use the running event with this project name, instead of the last event")
+                .short('p')
+                .long("project"),
+        )
+        .arg(crate::quiet_arg())
+}
+
+pub fn command() -> Command {
+    pause_command("pause", "Start a break in the running event")
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut event = if let Some(project) = args.get_one::<String>("project") {
+        find_running_events()
+            .into_iter()
+            .find(|e| e.project == *project)
+            .ok_or_else(|| anyhow!("No running event for project \"{project}\""))?
+    } else if let Some(running) = find_running_events().pop() {
+        running
+    } else {
+        find_last_event()?
+    };
+    let at = to_time_arg(args, "time", &event.start)?;
+
+    event.pause(at)?;
+    event.save()?;
+    hooks::run(&["on-save"], &event);
+
+    if !args.get_flag("quiet") {
+        print_table(event.to_table("Paused"), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
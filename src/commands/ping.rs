@@ -0,0 +1,149 @@
+use crate::config::detect_project;
+use crate::event::{TimeEvent, find_last_activity, find_running_events, save_activity};
+use crate::git::add_branch_info;
+use crate::hooks;
+use crate::utils::idle_duration;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    Command::new("ping")
+        .about("Record activity and track the project automatically, such as from the shell prompt")
+        .arg(
+            Arg::new("project")
+                .help("The project to track, instead of the project for the current directory")
+                .short('p')
+                .long("project"),
+        )
+}
+
+fn start_event(project: String, start: chrono::NaiveDateTime) -> Result<(), anyhow::Error> {
+    let mut event = TimeEvent {
+        project,
+        start,
+        ..TimeEvent::default()
+    };
+
+    add_branch_info(&mut event)?;
+    event.save()?;
+    hooks::run(&["on-start", "on-save"], &event);
+    Ok(())
+}
+
+/// Returns the running events to stop, with the stop time, and if the project
+/// should be started. Nothing is stopped if the project is already running,
+/// since the other running events were started with "tt start --parallel".
+fn plan(
+    mut running: Vec<TimeEvent>,
+    project: Option<&str>,
+    now: chrono::NaiveDateTime,
+    idle_since: Option<chrono::NaiveDateTime>,
+) -> (Vec<(TimeEvent, chrono::NaiveDateTime)>, bool) {
+    let mut stop = vec![];
+
+    // Events without any activity, such as events started with "tt start", are kept running
+    if let Some(last_activity) = idle_since {
+        let (idle, active): (Vec<_>, Vec<_>) =
+            running.into_iter().partition(|e| e.start < last_activity);
+        stop.extend(idle.into_iter().map(|e| (e, last_activity)));
+        running = active;
+    }
+
+    // Directories without a known project, such as ~/Downloads, are not tracked
+    let Some(project) = project else {
+        return (stop, false);
+    };
+
+    if running.iter().any(|e| e.project == project) {
+        return (stop, false);
+    }
+
+    stop.extend(running.into_iter().map(|e| (e, now)));
+    (stop, true)
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let now = chrono::Local::now().naive_local();
+
+    // Stop the running events at the last activity, if the user has been idle for too long
+    let idle = idle_duration()?;
+    let idle_since = find_last_activity().filter(|at| (now - *at).num_seconds() > idle);
+    let project = match args.get_one::<String>("project") {
+        Some(project) => Some(project.clone()),
+        None => detect_project(&std::env::current_dir()?)?,
+    };
+
+    let (stop, start) = plan(find_running_events(), project.as_deref(), now, idle_since);
+    for (mut event, at) in stop {
+        let saved = event.stop_at(at)?;
+        hooks::run_stopped(&event, saved);
+    }
+
+    if start && let Some(project) = project {
+        start_event(project, now)?;
+    }
+
+    save_activity(&now)?;
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running(project: &str, start: &str) -> TimeEvent {
+        TimeEvent {
+            project: project.to_string(),
+            start: chrono::NaiveDateTime::parse_from_str(start, "%Y-%m-%d %H:%M").unwrap(),
+            ..TimeEvent::default()
+        }
+    }
+
+    fn stopped(plan: &[(TimeEvent, chrono::NaiveDateTime)]) -> Vec<(&str, String)> {
+        plan.iter()
+            .map(|(e, at)| (e.project.as_str(), at.format("%H:%M").to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_plan() {
+        let now = running("", "2025-09-08 12:00").start;
+        let events = vec![
+            running("work", "2025-09-08 08:00"),
+            running("oncall", "2025-09-08 10:00"),
+        ];
+
+        // A parallel event is kept running when the project is already tracked
+        let (stop, start) = plan(events.clone(), Some("work"), now, None);
+        assert_eq!(stopped(&stop), vec![]);
+        assert!(!start);
+
+        let (stop, start) = plan(events.clone(), Some("oncall"), now, None);
+        assert_eq!(stopped(&stop), vec![]);
+        assert!(!start);
+
+        let (stop, start) = plan(events.clone(), None, now, None);
+        assert_eq!(stopped(&stop), vec![]);
+        assert!(!start);
+
+        // All running events are stopped, like "tt start" without --parallel
+        let (stop, start) = plan(events.clone(), Some("app-tt"), now, None);
+        assert_eq!(
+            stopped(&stop),
+            vec![("work", "12:00".into()), ("oncall", "12:00".into())]
+        );
+        assert!(start);
+
+        // Idle since 09:00, so "work" is stopped and started again
+        let idle_since = Some(running("", "2025-09-08 09:00").start);
+        let (stop, start) = plan(events.clone(), Some("work"), now, idle_since);
+        assert_eq!(
+            stopped(&stop),
+            vec![("work", "09:00".into()), ("oncall", "12:00".into())]
+        );
+        assert!(start);
+
+        let (stop, start) = plan(events, Some("oncall"), now, idle_since);
+        assert_eq!(stopped(&stop), vec![("work", "09:00".into())]);
+        assert!(!start);
+    }
+}
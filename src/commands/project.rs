@@ -0,0 +1,102 @@
+use crate::event::{find_events, validate_project};
+use crate::hooks;
+use crate::styling::{plain_table, print_table, regular_table};
+use crate::utils::{format_date, to_naive_date_time, to_since_date_time};
+use clap::{Arg, Command};
+use prettytable::{Table, row};
+
+pub fn command() -> Command {
+    Command::new("project")
+        .about("Manage projects")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("rename")
+                .about("Rename a project in all matching events")
+                .arg(
+                    Arg::new("old")
+                        .help("The current project name")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("new")
+                        .help("The new project name")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(
+                    Arg::new("since")
+                        .help("From what start time for event(s) to rename")
+                        .long("since"),
+                )
+                .arg(
+                    Arg::new("until")
+                        .help("Until what start time for event(s) to rename")
+                        .long("until"),
+                )
+                .arg(crate::dry_run_arg()),
+        )
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    match args.subcommand() {
+        Some(("rename", args)) => rename(args),
+        _ => unreachable!("subcommand_required"),
+    }
+}
+
+fn rename(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let old = args.get_one::<String>("old").expect("Required");
+    let new = args.get_one::<String>("new").expect("Required");
+    validate_project(new)?;
+
+    let since = to_since_date_time(args.get_one::<String>("since"))?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+    let dry_run = args.get_flag("dry_run");
+
+    let mut report = Table::new();
+    report.set_titles(row!["Date", "Start", "From", "To", "Status"]);
+
+    let mut total_renamed = 0;
+    let mut total_failed = 0;
+    for mut event in find_events(&since.date(), &until.date()) {
+        if event.project != *old || event.start < since || event.start > until {
+            continue;
+        }
+
+        let old_path = event.source_path();
+        event.project = new.clone();
+
+        let status = if event.path().exists() {
+            total_failed += 1;
+            "Already exists".to_string()
+        } else if dry_run {
+            total_renamed += 1;
+            "Would rename".to_string()
+        } else if let Err(e) = event.move_from(&old_path) {
+            total_failed += 1;
+            e.to_string()
+        } else {
+            total_renamed += 1;
+            hooks::run_moved(&event, &old_path);
+            "Renamed".to_string()
+        };
+
+        report.add_row(row![
+            format_date(&event.start, "ymd"),
+            format_date(&event.start, "hm"),
+            old_path.to_string_lossy(),
+            event.path().to_string_lossy(),
+            status,
+        ]);
+    }
+
+    let mut summary = Table::new();
+    summary.add_row(row!["Total renamed:", total_renamed.to_string()]);
+    summary.add_row(row!["Total failed:", total_failed.to_string()]);
+
+    print_table(report, regular_table(), [1, 1]);
+    print_table(summary, plain_table(), [0, 1]);
+
+    Ok(if total_failed > 0 { 1 } else { 0 })
+}
@@ -0,0 +1,106 @@
+use crate::event::{TimeEvent, find_events};
+use crate::styling::{print_table, regular_table};
+use crate::utils::{format_date, format_duration, to_naive_date_time, to_since_date_time};
+use clap::{Arg, Command};
+use prettytable::{Cell, Row, Table, row};
+
+pub struct Usage {
+    pub name: String,
+    pub first: chrono::NaiveDateTime,
+    pub last: chrono::NaiveDateTime,
+    pub events: usize,
+    pub duration: chrono::Duration,
+}
+
+pub fn list_command(name: &'static str, about: &'static str) -> Command {
+    Command::new(name)
+        .about(about)
+        .arg(Arg::new("since").help("From what start time").long("since"))
+        .arg(
+            Arg::new("until")
+                .help("Until what start time")
+                .long("until"),
+        )
+        .arg(
+            Arg::new("sort")
+                .help("Sort by name, first/last seen, or most events/time")
+                .short('s')
+                .long("sort")
+                .value_parser(["name", "first", "last", "events", "time"])
+                .default_value("name"),
+        )
+        .arg(crate::quiet_arg().help("Only print the names, one per line"))
+}
+
+pub fn command() -> Command {
+    list_command("projects", "List projects with statistics")
+}
+
+/// Collects usage statistics for each name returned by `names_of()`
+pub fn find_usage(
+    args: &clap::ArgMatches,
+    names_of: impl Fn(&TimeEvent) -> Vec<String>,
+) -> Result<Vec<Usage>, anyhow::Error> {
+    let since = to_since_date_time(args.get_one::<String>("since"))?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+
+    let mut usage: Vec<Usage> = vec![];
+    for event in find_events(&since.date(), &until.date()) {
+        if event.start < since || event.start > until {
+            continue;
+        }
+
+        for name in names_of(&event) {
+            if let Some(u) = usage.iter_mut().find(|u| u.name == name) {
+                u.last = event.start;
+                u.events += 1;
+                u.duration += event.duration();
+            } else {
+                usage.push(Usage {
+                    name,
+                    first: event.start,
+                    last: event.start,
+                    events: 1,
+                    duration: event.duration(),
+                });
+            }
+        }
+    }
+
+    match args.get_one::<String>("sort").map(|s| s.as_str()) {
+        Some("first") => usage.sort_by_key(|u| u.first),
+        Some("last") => usage.sort_by_key(|u| u.last),
+        Some("events") => usage.sort_by_key(|u| std::cmp::Reverse(u.events)),
+        Some("time") => usage.sort_by_key(|u| std::cmp::Reverse(u.duration)),
+        _ => usage.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+
+    Ok(usage)
+}
+
+pub fn print_usage(args: &clap::ArgMatches, title: &str, usage: Vec<Usage>) {
+    if args.get_flag("quiet") {
+        usage.iter().for_each(|u| println!("{}", u.name));
+        return;
+    }
+
+    let mut report = Table::new();
+    report.set_titles(row![title, "First seen", "Last seen", "Events", "Duration"]);
+    for u in usage {
+        report.add_row(Row::new(vec![
+            Cell::new(&u.name),
+            Cell::new(&format_date(&u.first, "ymd")),
+            Cell::new(&format_date(&u.last, "ymd")),
+            Cell::new(&u.events.to_string()).style_spec("r"),
+            Cell::new(&format_duration(&u.duration)).style_spec("r"),
+        ]));
+    }
+
+    print_table(report, regular_table(), [1, 1]);
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let usage = find_usage(args, |e| vec![e.project.clone()])?;
+    print_usage(args, "Project", usage);
+    Ok(0)
+}
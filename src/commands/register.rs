@@ -1,4 +1,5 @@
 use crate::event::TimeEvent;
+use crate::hooks;
 use crate::styling::{plain_table, print_table};
 use crate::utils::to_naive_date_time;
 use clap::{Arg, Command};
@@ -69,10 +70,11 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
         start,
         stop: Some(stop),
         tags,
-        total_duration: None,
+        ..TimeEvent::default()
     };
 
     event.save()?;
+    hooks::run(&["on-save"], &event);
     print_table(event.to_table("Saved"), plain_table(), [1, 1]);
 
     Ok(0)
@@ -23,6 +23,7 @@ pub fn command() -> Command {
                 .short('t')
                 .long("tag"),
         )
+        .arg(crate::filter_arg())
         .arg(
             Arg::new("since")
                 .help("From what time")
@@ -88,6 +89,7 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
                     start: event.start,
                     stop: event.stop,
                     tags: event.tags.clone(),
+                    ..TimeEvent::default()
                 };
             } else if is_same_date(&grouped_event.start, &event.start)
                 && grouped_event.project == event.project
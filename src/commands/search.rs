@@ -0,0 +1,91 @@
+use crate::event::{TimeEvent, find_events};
+use crate::styling::{print_table, regular_table};
+use crate::utils::{format_date, format_duration, to_naive_date_time, to_since_date_time};
+use clap::{Arg, ArgAction, Command};
+use prettytable::{Cell, Row, Table, row};
+use regex::Regex;
+
+pub fn command() -> Command {
+    Command::new("search")
+        .about("Search event descriptions, projects and tags")
+        .arg(
+            Arg::new("text")
+                .help("The text to search for (case insensitive)")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::new("regex")
+                .help("Treat the text as a regular expression")
+                .short('r')
+                .long("regex")
+                .num_args(0)
+                .action(ArgAction::SetTrue),
+        )
+        .arg(Arg::new("since").help("From what start time").long("since"))
+        .arg(
+            Arg::new("until")
+                .help("Until what start time")
+                .long("until"),
+        )
+        .arg(crate::filter_arg())
+        .arg(crate::json_arg())
+}
+
+fn is_match(event: &TimeEvent, re: &Regex) -> bool {
+    re.is_match(&event.description)
+        || re.is_match(&event.project)
+        || event.tags.iter().any(|t| re.is_match(t))
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let text = args.get_one::<String>("text").expect("Required");
+    let text = if args.get_flag("regex") {
+        text.clone()
+    } else {
+        regex::escape(text)
+    };
+
+    let re = Regex::new(&format!("(?i){text}"))?;
+    let since = to_since_date_time(args.get_one::<String>("since"))?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+
+    let events = find_events(&since.date(), &until.date())
+        .into_iter()
+        .filter(|e| e.start >= since && e.start <= until)
+        .filter(|e| e.matches_args(args) && is_match(e, &re))
+        .collect::<Vec<TimeEvent>>();
+
+    if args.get_flag("json") {
+        let json = events
+            .iter()
+            .map(|e| e.to_json())
+            .collect::<Result<Vec<serde_json::Value>, anyhow::Error>>()?;
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(if events.is_empty() { 1 } else { 0 });
+    }
+
+    let mut report = Table::new();
+    report.set_titles(row![
+        "Date",
+        "Project",
+        "Start",
+        "Duration",
+        "Tags",
+        "Description"
+    ]);
+    for event in &events {
+        report.add_row(Row::new(vec![
+            Cell::new(&format_date(&event.start, "ymd")),
+            Cell::new(&event.project),
+            Cell::new(&format_date(&event.start, "hm")),
+            Cell::new(&format_duration(&event.duration())).style_spec("r"),
+            Cell::new(&event.tags_as_string()),
+            Cell::new(event.description()),
+        ]));
+    }
+
+    print_table(report, regular_table(), [1, 1]);
+
+    Ok(if events.is_empty() { 1 } else { 0 })
+}
@@ -0,0 +1,83 @@
+use crate::event::{TimeEvent, find_event, find_events};
+use crate::styling::{DASH, plain_table, print_table};
+use crate::utils::{format_date, format_duration};
+use clap::{Arg, Command};
+use prettytable::{Table, row};
+
+pub fn command() -> Command {
+    Command::new("show")
+        .about("Show all the details about a single event")
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time, as listed by 'tt log'")
+                .required(true)
+                .index(1),
+        )
+        .arg(crate::json_arg())
+}
+
+fn describe(event: Option<&TimeEvent>) -> String {
+    match event {
+        Some(e) => format!("{} {} {}", e.id(), format_date(&e.start, "full"), e.project),
+        None => DASH.to_string(),
+    }
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let event = find_event(args.get_one::<String>("id").expect("Required"))?;
+    let content = std::fs::read_to_string(event.source_path())?;
+    let raw = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content)?;
+
+    // Neighbours further away than a week are not very interesting
+    let week = chrono::Duration::days(7);
+    let events = find_events(&(event.start - week).date(), &(event.start + week).date());
+    let previous = events.iter().rev().find(|e| e.start < event.start);
+    let next = events.iter().find(|e| e.start > event.start);
+    let overlaps = events
+        .iter()
+        .filter(|e| e.id() != event.id() && e.overlaps(&event))
+        .collect::<Vec<&TimeEvent>>();
+
+    if args.get_flag("json") {
+        let json = serde_json::json!({
+            "id": event.id(),
+            "file": event.source_path(),
+            "fields": raw,
+            "seconds": event.duration().num_seconds(),
+            "previous": previous.map(|e| e.id()),
+            "next": next.map(|e| e.id()),
+            "overlaps": overlaps.iter().map(|e| e.id()).collect::<Vec<String>>(),
+        });
+
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(0);
+    }
+
+    let mut t = Table::new();
+    t.add_row(row!["Id", event.id()]);
+    t.add_row(row!["File", event.source_path().to_string_lossy()]);
+    for (key, value) in &raw {
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            serde_json::Value::Null => DASH.to_string(),
+            v => v.to_string(),
+        };
+
+        t.add_row(row![key, value]);
+    }
+
+    t.add_row(row![
+        "Computed duration",
+        format_duration(&event.duration())
+    ]);
+    t.add_row(row!["Previous", describe(previous)]);
+    t.add_row(row!["Next", describe(next)]);
+    for (i, overlap) in overlaps.iter().enumerate() {
+        let label = if i == 0 { "Overlaps" } else { "" };
+        t.add_row(row![label, describe(Some(overlap))]);
+    }
+
+    print_table(t, plain_table(), [1, 1]);
+
+    Ok(0)
+}
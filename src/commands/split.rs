@@ -0,0 +1,98 @@
+use crate::event::{TimeEvent, find_event, validate_project};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::to_naive_date_time;
+use anyhow::anyhow;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    Command::new("split")
+        .about("Split an event into two events")
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time, as listed by 'tt log'")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::new("split_time")
+                .help("When the second event starts (e.g., '14:30')")
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::new("project")
+                .help("Project name for the second event")
+                .short('p')
+                .long("project"),
+        )
+        .arg(
+            Arg::new("tag")
+                .help("Tag(s) for the second event")
+                .short('t')
+                .long("tag"),
+        )
+        .arg(
+            Arg::new("description")
+                .help("Description for the second event")
+                .short('d')
+                .long("description"),
+        )
+        .arg(crate::quiet_arg())
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut first = find_event(args.get_one::<String>("id").expect("Required"))?;
+    let split = to_naive_date_time(args.get_one::<String>("split_time"), Some(&first.start))?;
+
+    let stop = first
+        .stop
+        .unwrap_or_else(|| chrono::Local::now().naive_local());
+    if split <= first.start || split >= stop {
+        return Err(anyhow!(
+            "The split time must be between the start and stop time of the event"
+        ));
+    }
+
+    let mut second = TimeEvent {
+        start: split,
+        stop: first.stop,
+        source: None,
+        ..first.clone()
+    };
+
+    if let Some(project) = args.get_one::<String>("project") {
+        validate_project(project)?;
+        second.project = project.clone();
+    }
+
+    if let Some(tag) = args.get_one::<String>("tag") {
+        second.tags = tag.split(',').map(|s| s.trim().to_string()).collect();
+    }
+
+    if let Some(description) = args.get_one::<String>("description") {
+        second.description = description.clone();
+    }
+
+    if second.path().exists() {
+        return Err(anyhow!("{:?} already exists", second.path()));
+    }
+
+    // A break across the split time is kept in both events
+    first.stop = Some(split);
+    first.breaks.retain(|b| b.start < split);
+    second
+        .breaks
+        .retain(|b| b.stop.is_none_or(|stop| stop > split));
+    first.save()?;
+    second.save()?;
+    hooks::run(&["on-save"], &first);
+    hooks::run(&["on-save"], &second);
+
+    if !args.get_flag("quiet") {
+        print_table(first.to_table("Saved"), plain_table(), [1, 0]);
+        print_table(second.to_table("Created"), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
@@ -1,6 +1,9 @@
-use crate::event::{TimeEvent, find_last_event};
+use crate::event::{TimeEvent, find_last_event, find_running_events, validate_project};
+use crate::git::add_branch_info;
+use crate::hooks;
 use crate::styling::{plain_table, print_table};
-use crate::utils::{default_project, format_date, min_duration, to_naive_date_time};
+use crate::utils::{default_project, format_date, to_naive_date_time};
+use anyhow::anyhow;
 use clap::{Arg, Command};
 
 pub fn command() -> clap::Command {
@@ -35,6 +38,13 @@ pub fn command() -> clap::Command {
                 .default_missing_value("600")
                 .value_parser(clap::value_parser!(i64)),
         )
+        .arg(
+            Arg::new("parallel")
+                .help("Keep the running events of other projects running")
+                .long("parallel")
+                .num_args(0)
+                .action(clap::ArgAction::SetTrue),
+        )
         .arg(crate::quiet_arg())
 }
 
@@ -52,6 +62,8 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
     let start = to_naive_date_time(args.get_one::<String>("start_time"), None)?;
     let mut last = find_last_event().unwrap_or_default();
 
+    let running = find_running_events();
+
     let mut status = "Started";
     let resume = args.get_one::<i64>("resume");
     let project = args
@@ -67,28 +79,21 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
         status = "Resumed";
         last.stop = None;
         last
-    } else if !last.project.is_empty() && last.stop.is_none() {
+    } else if let Some(running) = running.iter().find(|e| e.project == project) {
         status = "Tracking";
-        last
+        running.clone()
     } else {
-        // Stop current event if not already stopped
-        if !last.project.is_empty() && last.stop.is_none() {
-            last.stop = Some(start);
-            if last.duration().num_seconds() < min_duration()? {
-                last.delete()?;
-            } else {
-                last.save()?;
-            }
-        }
-
-        TimeEvent {
+        let mut event = TimeEvent {
             description: "".to_string(),
             project,
             start,
             stop: None,
             tags: vec![],
-            total_duration: None,
-        }
+            ..TimeEvent::default()
+        };
+
+        add_branch_info(&mut event)?;
+        event
     };
 
     if let Some(description) = args.get_one::<String>("description") {
@@ -99,7 +104,50 @@ pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
         event.add_tags(tag.split(',').map(|s| s.trim().to_string()).collect());
     }
 
-    event.save()?;
+    // Stop the running events, unless they should be tracked in parallel
+    let running = if status != "Tracking" && !args.get_flag("parallel") {
+        running
+    } else {
+        vec![]
+    };
+
+    // Validate everything before any file is changed
+    validate_project(&event.project)?;
+    if let Some(running) = running.iter().find(|e| start <= e.start) {
+        return Err(anyhow!(
+            "Cannot start before the running event, which started at {}",
+            format_date(&running.start, "full")
+        ));
+    }
+
+    if status == "Started" && event.path().exists() {
+        return Err(anyhow!("{:?} already exists", event.path()));
+    }
+
+    let mut stopped = vec![];
+    for mut event in running.clone() {
+        let saved = event.stop_at(start)?;
+        stopped.push((event, saved));
+    }
+
+    // Restore the running events if the new event could not be saved
+    if let Err(e) = event.save() {
+        for running in running {
+            running.save()?;
+        }
+
+        return Err(e);
+    }
+
+    for (stopped, saved) in &stopped {
+        hooks::run_stopped(stopped, *saved);
+    }
+
+    if status == "Tracking" {
+        hooks::run(&["on-save"], &event);
+    } else {
+        hooks::run(&["on-start", "on-save"], &event);
+    }
 
     if !args.get_flag("quiet") {
         print_table(event.to_table(status), plain_table(), [1, 1]);
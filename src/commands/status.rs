@@ -1,4 +1,4 @@
-use crate::event::find_last_event;
+use crate::event::{find_last_event, find_running_events};
 use crate::styling::{plain_table, print_table};
 use crate::utils::format_date;
 use clap::Command;
@@ -9,23 +9,32 @@ pub fn command() -> Command {
 }
 
 pub fn run(_args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
-    let last = find_last_event()?;
-    let status = if last.stop.is_some() {
-        "Stopped"
-    } else {
-        "Tracking"
-    };
+    let mut events = find_running_events();
+    if events.is_empty() {
+        events.push(find_last_event()?);
+    }
 
-    let mut t = last.to_table(status);
-    t.insert_row(
-        3,
-        row![
-            "Now",
-            format_date(&chrono::Local::now().naive_local(), "full")
-        ],
-    );
+    let last = events.len() - 1;
+    for (i, event) in events.into_iter().enumerate() {
+        let status = if event.stop.is_some() {
+            "Stopped"
+        } else if event.is_paused() {
+            "Paused"
+        } else {
+            "Tracking"
+        };
 
-    print_table(t, plain_table(), [1, 1]);
+        let mut t = event.to_table(status);
+        t.insert_row(
+            3,
+            row![
+                "Now",
+                format_date(&chrono::Local::now().naive_local(), "full")
+            ],
+        );
+
+        print_table(t, plain_table(), [1, usize::from(i == last)]);
+    }
 
     Ok(0)
 }
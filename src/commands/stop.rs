@@ -1,7 +1,9 @@
-use crate::event::find_last_event;
+use crate::event::{find_event, find_last_activity, find_last_event, find_running_events};
+use crate::hooks;
 use crate::styling::{plain_table, print_table};
-use crate::utils::{default_project, format_date, min_duration, to_naive_date_time};
-use clap::{Arg, Command};
+use crate::utils::{default_project, format_date, format_duration, max_duration, to_time_arg};
+use anyhow::anyhow;
+use clap::{Arg, ArgAction, Command};
 
 pub fn command() -> Command {
     let now = chrono::Local::now().naive_local();
@@ -16,38 +18,80 @@ pub fn command() -> Command {
         )
         .arg(
             Arg::new("project")
+                .help("Stop the running event with this project name, instead of the last event")
+                .short('p')
+                .long("project")
+                .conflicts_with("id"),
+        )
+        .arg(
+            Arg::new("tag_unless_same_project")
                 .help("Add a tag, unless same project as last event")
                 .long("tag-unless-same-project")
                 .num_args(0..=1)
                 .default_missing_value(default_project()),
         )
+        .arg(
+            Arg::new("id")
+                .help("Event id or start time to stop, instead of the last event")
+                .long("id"),
+        )
+        .arg(
+            Arg::new("at_last_activity")
+                .help("Stop at the last activity recorded by 'tt ping', instead of now")
+                .long("at-last-activity")
+                .num_args(0)
+                .action(ArgAction::SetTrue)
+                .conflicts_with("stop_time"),
+        )
         .arg(crate::quiet_arg())
 }
 
 pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
-    let mut last = find_last_event()?;
+    let mut last = if let Some(id) = args.get_one::<String>("id") {
+        find_event(id)?
+    } else if let Some(project) = args.get_one::<String>("project") {
+        find_running_events()
+            .into_iter()
+            .find(|e| e.project == *project)
+            .ok_or_else(|| anyhow!("No running event for project \"{project}\""))?
+    } else if let Some(running) = find_running_events().pop() {
+        running
+    } else {
+        find_last_event()?
+    };
     let mut status = "Stopped";
 
     if last.stop.is_none() {
-        if let Some(project) = args.get_one::<String>("project")
+        if let Some(project) = args.get_one::<String>("tag_unless_same_project")
             && last.project != *project
         {
             status = "Added tag";
             last.add_tags(vec![project]);
             last.save()?;
+            hooks::run(&["on-save"], &last);
         } else {
-            last.stop = Some(to_naive_date_time(
-                args.get_one::<String>("stop_time"),
-                Some(&last.start),
-            )?);
+            let stop = if args.get_flag("at_last_activity") {
+                find_last_activity()
+                    .filter(|at| *at > last.start)
+                    .ok_or_else(|| anyhow!("No activity recorded since the event started"))?
+            } else {
+                to_time_arg(args, "stop_time", &last.start)?
+            };
 
-            if last.duration().num_seconds() < min_duration()? {
+            let saved = last.stop_at(stop)?;
+            hooks::run_stopped(&last, saved);
+            if saved {
+                status = "Saved";
+            } else {
                 status = "Discarded";
-                last.delete()?;
                 last.description = "Event duration is lower than TT_MIN_DURATION".to_string();
-            } else {
-                status = "Saved";
-                last.save()?;
+            }
+
+            if last.duration().num_seconds() > max_duration()? {
+                eprintln!(
+                    "Warning: The event lasted {}, which is longer than TT_MAX_DURATION. Use --at-last-activity or \"tt amend --stop\" to change the stop time.",
+                    format_duration(&last.duration())
+                );
             }
         }
     }
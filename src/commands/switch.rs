@@ -0,0 +1,102 @@
+use crate::event::{TimeEvent, find_running_events, validate_project};
+use crate::git::add_branch_info;
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::{format_date, to_naive_date_time};
+use anyhow::anyhow;
+use clap::{Arg, Command};
+
+pub fn command() -> Command {
+    let now = chrono::Local::now().naive_local();
+
+    Command::new("switch")
+        .about("Stop the running events and start tracking another project")
+        .arg(
+            Arg::new("project")
+                .help("Project name for the new event")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::new("start_time")
+                .help("When to stop the running event and start the new event (e.g., '14:30')")
+                .default_value(format_date(&now, "hm"))
+                .index(2),
+        )
+        .arg(Arg::new("tag").help("Event tag(s)").short('t').long("tag"))
+        .arg(
+            Arg::new("description")
+                .help("Event description")
+                .short('d')
+                .long("description"),
+        )
+        .arg(crate::quiet_arg())
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let at = to_naive_date_time(args.get_one::<String>("start_time"), None)?;
+    let running = find_running_events();
+    let project = args.get_one::<String>("project").expect("Required");
+    validate_project(project)?;
+
+    let mut event = TimeEvent {
+        project: project.clone(),
+        start: at,
+        ..TimeEvent::default()
+    };
+
+    add_branch_info(&mut event)?;
+
+    if let Some(description) = args.get_one::<String>("description") {
+        event.description = description.clone();
+    }
+
+    if let Some(tag) = args.get_one::<String>("tag") {
+        event.add_tags(tag.split(',').map(|s| s.trim().to_string()).collect());
+    }
+
+    // Validate everything before any file is changed
+    if let Some(running) = running.iter().find(|e| at <= e.start) {
+        return Err(anyhow!(
+            "Cannot switch before the running event, which started at {}",
+            format_date(&running.start, "full")
+        ));
+    }
+
+    if event.path().exists() {
+        return Err(anyhow!("{:?} already exists", event.path()));
+    }
+
+    let mut stopped = vec![];
+    for mut event in running.clone() {
+        let saved = event.stop_at(at)?;
+        stopped.push((event, saved));
+    }
+
+    // Restore the running events if the new event could not be saved
+    if let Err(e) = event.save() {
+        for running in running {
+            running.save()?;
+        }
+
+        return Err(e);
+    }
+
+    // The hooks are run when both events are written, since the stop can be undone
+    for (stopped, saved) in &stopped {
+        hooks::run_stopped(stopped, *saved);
+    }
+
+    hooks::run(&["on-start", "on-save"], &event);
+
+    if !args.get_flag("quiet") {
+        for (stopped, saved) in &stopped {
+            let status = if *saved { "Stopped" } else { "Discarded" };
+            print_table(stopped.to_table(status), plain_table(), [1, 0]);
+        }
+
+        print_table(event.to_table("Started"), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
@@ -0,0 +1,132 @@
+use crate::event::find_events;
+use crate::hooks;
+use crate::styling::{plain_table, print_table, regular_table};
+use crate::utils::{format_date, to_naive_date_time, to_since_date_time};
+use clap::{Arg, Command};
+use prettytable::{Table, row};
+
+fn filter_args(cmd: Command) -> Command {
+    cmd.arg(
+        Arg::new("project")
+            .help("Only change events with this project name")
+            .short('p')
+            .long("project"),
+    )
+    .arg(
+        Arg::new("since")
+            .help("From what start time for event(s) to change")
+            .long("since"),
+    )
+    .arg(
+        Arg::new("until")
+            .help("Until what start time for event(s) to change")
+            .long("until"),
+    )
+    .arg(crate::filter_arg())
+    .arg(crate::dry_run_arg())
+}
+
+pub fn command() -> Command {
+    Command::new("tag")
+        .about("Manage tags")
+        .subcommand_required(true)
+        .subcommand(filter_args(
+            Command::new("rename")
+                .about("Rename a tag in all matching events")
+                .arg(
+                    Arg::new("old")
+                        .help("The current tag")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(Arg::new("new").help("The new tag").required(true).index(2)),
+        ))
+        .subcommand(filter_args(
+            Command::new("merge")
+                .about("Replace multiple tags with a single tag")
+                .arg(
+                    Arg::new("old")
+                        .help("Comma separated list of tags to merge")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(Arg::new("new").help("The new tag").required(true).index(2)),
+        ))
+        .subcommand(filter_args(
+            Command::new("remove")
+                .about("Remove tag(s) from all matching events")
+                .arg(
+                    Arg::new("old")
+                        .help("Comma separated list of tags to remove")
+                        .required(true)
+                        .index(1),
+                ),
+        ))
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let Some((_, args)) = args.subcommand() else {
+        unreachable!("subcommand_required");
+    };
+
+    let old = args
+        .get_one::<String>("old")
+        .expect("Required")
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>();
+
+    let new = args.try_get_one::<String>("new").ok().flatten().cloned();
+    let since = to_since_date_time(args.get_one::<String>("since"))?;
+    let until = to_naive_date_time(args.get_one::<String>("until"), None)?;
+    let dry_run = args.get_flag("dry_run");
+
+    let mut report = Table::new();
+    report.set_titles(row![
+        "Date", "Project", "Start", "Before", "After", "Status"
+    ]);
+
+    let mut total_events = 0;
+    for mut event in find_events(&since.date(), &until.date()) {
+        if event.start < since || event.start > until || !event.matches_args(args) {
+            continue;
+        }
+
+        if !old.iter().any(|t| event.has_tag(t)) {
+            continue;
+        }
+
+        let before = event.tags_as_string();
+        event.remove_tags(&old);
+        if let Some(new) = &new {
+            event.add_tags(vec![new]);
+        }
+
+        let status = if dry_run {
+            "Would save"
+        } else {
+            event.save()?;
+            hooks::run(&["on-save"], &event);
+            "Saved"
+        };
+
+        total_events += 1;
+        report.add_row(row![
+            format_date(&event.start, "ymd"),
+            event.project,
+            format_date(&event.start, "hm"),
+            before,
+            event.tags_as_string(),
+            status,
+        ]);
+    }
+
+    let mut summary = Table::new();
+    summary.add_row(row!["Total events:", total_events.to_string()]);
+
+    print_table(report, regular_table(), [1, 1]);
+    print_table(summary, plain_table(), [0, 1]);
+
+    Ok(0)
+}
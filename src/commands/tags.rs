@@ -0,0 +1,12 @@
+use crate::commands::projects::{find_usage, list_command, print_usage};
+use clap::Command;
+
+pub fn command() -> Command {
+    list_command("tags", "List tags with statistics")
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let usage = find_usage(args, |e| e.tags.clone())?;
+    print_usage(args, "Tag", usage);
+    Ok(0)
+}
@@ -0,0 +1,35 @@
+use crate::commands::pause::pause_command;
+use crate::event::{find_last_event, find_running_events};
+use crate::hooks;
+use crate::styling::{plain_table, print_table};
+use crate::utils::to_time_arg;
+use anyhow::anyhow;
+use clap::Command;
+
+pub fn command() -> Command {
+    pause_command("unpause", "End the break in the running event")
+}
+
+pub fn run(args: &clap::ArgMatches) -> Result<i32, anyhow::Error> {
+    let mut event = if let Some(project) = args.get_one::<String>("project") {
+        find_running_events()
+            .into_iter()
+            .find(|e| e.project == *project && e.is_paused())
+            .ok_or_else(|| anyhow!("No paused event for project \"{project}\""))?
+    } else if let Some(paused) = find_running_events().into_iter().rfind(|e| e.is_paused()) {
+        paused
+    } else {
+        find_last_event()?
+    };
+    let at = to_time_arg(args, "time", &event.start)?;
+
+    event.unpause(at)?;
+    event.save()?;
+    hooks::run(&["on-save"], &event);
+
+    if !args.get_flag("quiet") {
+        print_table(event.to_table("Tracking"), plain_table(), [1, 1]);
+    }
+
+    Ok(0)
+}
@@ -0,0 +1,112 @@
+use crate::event::{TimeEvent, find_events, find_last_event};
+use crate::utils::format_date;
+use clap::Command;
+use clap_complete::engine::{ArgValueCandidates, ArgValueCompleter, CompletionCandidate};
+use std::ffi::OsStr;
+
+// Completion runs on every TAB press, so only the newest part of the history is read
+fn recent_events(days: i64) -> Vec<TimeEvent> {
+    let Ok(last) = find_last_event() else {
+        return vec![];
+    };
+
+    let since = last.start.date() - chrono::Duration::days(days);
+    find_events(&since, &chrono::NaiveDate::MAX)
+}
+
+fn project_candidates() -> Vec<CompletionCandidate> {
+    let mut projects = recent_events(365)
+        .into_iter()
+        .map(|e| e.project)
+        .collect::<Vec<String>>();
+
+    projects.sort();
+    projects.dedup();
+    projects.into_iter().map(CompletionCandidate::new).collect()
+}
+
+// Tags are given as a comma separated list, so only the last tag is completed
+fn tag_completer(current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    let (prefix, last) = match current.rfind(',') {
+        Some(i) => (&current[..=i], &current[i + 1..]),
+        None => ("", current.as_ref()),
+    };
+
+    let given = prefix.split(',').collect::<Vec<&str>>();
+    let mut tags = recent_events(365)
+        .into_iter()
+        .flat_map(|e| e.tags)
+        .filter(|t| t.starts_with(last) && !given.contains(&t.as_str()))
+        .collect::<Vec<String>>();
+
+    tags.sort();
+    tags.dedup();
+    tags.into_iter()
+        .map(|t| CompletionCandidate::new(t).add_prefix(prefix))
+        .collect()
+}
+
+fn id_candidates() -> Vec<CompletionCandidate> {
+    recent_events(62)
+        .into_iter()
+        .rev()
+        .take(50)
+        .map(|e| {
+            let help = format!("{} {}", format_date(&e.start, "full"), e.project);
+            CompletionCandidate::new(e.id()).help(Some(help.into()))
+        })
+        .collect()
+}
+
+fn time_candidates() -> Vec<CompletionCandidate> {
+    let now = chrono::Local::now().naive_local();
+    vec![
+        CompletionCandidate::new(format_date(&now, "hm")),
+        CompletionCandidate::new(format_date(&now, "ymd")),
+        CompletionCandidate::new("now"),
+    ]
+}
+
+/// Adds dynamic completion of projects, tags and times to all matching arguments
+pub fn add_candidates(parent: &str, mut cmd: Command) -> Command {
+    let name = cmd.get_name().to_string();
+    let ids = cmd
+        .get_arguments()
+        .map(|a| a.get_id().to_string())
+        .collect::<Vec<String>>();
+
+    for id in ids {
+        let is_tag = ["tag", "add_tag", "remove_tag"].contains(&id.as_str())
+            || (parent == "tag" && (id == "old" || id == "new"));
+        let is_project = ["project", "tag_unless_same_project"].contains(&id.as_str())
+            || (parent == "project" && (id == "old" || id == "new"));
+        let is_time = ["since", "until", "start_time", "stop_time", "time"].contains(&id.as_str());
+        let is_id = id == "id";
+
+        cmd = cmd.mut_arg(&id, |arg| {
+            if is_tag {
+                arg.add(ArgValueCompleter::new(tag_completer))
+            } else if is_project {
+                arg.add(ArgValueCandidates::new(project_candidates))
+            } else if is_time {
+                arg.add(ArgValueCandidates::new(time_candidates))
+            } else if is_id {
+                arg.add(ArgValueCandidates::new(id_candidates))
+            } else {
+                arg
+            }
+        });
+    }
+
+    let subcommands = cmd
+        .get_subcommands()
+        .map(|c| c.get_name().to_string())
+        .collect::<Vec<String>>();
+
+    for sub in subcommands {
+        cmd = cmd.mut_subcommand(&sub, |c| add_candidates(&name, c));
+    }
+
+    cmd
+}
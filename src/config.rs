@@ -0,0 +1,163 @@
+use crate::filter::glob_to_regex;
+use crate::git::git_root;
+use anyhow::anyhow;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Read from ~/.config/tt/config.toml
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub directory: Vec<DirectoryConfig>,
+    /// Settings for a project, unless set in the .tt.toml file
+    pub project: BTreeMap<String, ProjectConfig>,
+}
+
+/// Maps a directory pattern, such as "~/clients/acme/*", to a project name
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryConfig {
+    pub path: String,
+    pub project: String,
+}
+
+/// Read from .tt.toml in the project directory
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    pub project: Option<String>,
+    /// Add the git branch as a tag. Default is true.
+    pub branch_tag: Option<bool>,
+    /// Add issue keys from the branch name as "tag", "description" or "none". Default is "tag".
+    pub issue_keys: Option<String>,
+    /// Regular expression for issue keys. Default is "[A-Z][A-Z0-9]+-[0-9]+".
+    pub issue_pattern: Option<String>,
+}
+
+impl ProjectConfig {
+    fn or(self, other: ProjectConfig) -> ProjectConfig {
+        ProjectConfig {
+            project: self.project.or(other.project),
+            branch_tag: self.branch_tag.or(other.branch_tag),
+            issue_keys: self.issue_keys.or(other.issue_keys),
+            issue_pattern: self.issue_pattern.or(other.issue_pattern),
+        }
+    }
+}
+
+pub fn config_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_default();
+    Path::new(&home).join(".config").join("tt")
+}
+
+fn read_toml<T: for<'a> Deserialize<'a> + Default>(path: &Path) -> Result<T, anyhow::Error> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => toml::from_str(&content).map_err(|e| anyhow!("Invalid {path:?}: {e}")),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+        Err(e) => Err(anyhow!("Unable to read {path:?}: {e}")),
+    }
+}
+
+pub fn load_config() -> Result<Config, anyhow::Error> {
+    read_toml(&config_dir().join("config.toml"))
+}
+
+/// Finds the settings for a project, from the closest .tt.toml file and the config
+pub fn project_config(dir: &Path, project: &str) -> Result<ProjectConfig, anyhow::Error> {
+    let from_config = load_config()?.project.remove(project).unwrap_or_default();
+    match dir
+        .ancestors()
+        .map(|d| d.join(".tt.toml"))
+        .find(|p| p.is_file())
+    {
+        Some(path) => Ok(read_toml::<ProjectConfig>(&path)?.or(from_config)),
+        None => Ok(from_config),
+    }
+}
+
+/// Finds the project for a directory, by looking for the closest .tt-project
+/// or .tt.toml file, then the directory patterns in the config, and last the
+/// name of the git repository.
+pub fn detect_project(dir: &Path) -> Result<Option<String>, anyhow::Error> {
+    detect_project_with_config(dir, &load_config()?)
+}
+
+fn detect_project_with_config(
+    dir: &Path,
+    config: &Config,
+) -> Result<Option<String>, anyhow::Error> {
+    for dir in dir.ancestors() {
+        let path = dir.join(".tt-project");
+        if path.is_file() {
+            let content = std::fs::read_to_string(&path)?;
+            if let Some(project) = content.lines().next().map(|l| l.trim())
+                && !project.is_empty()
+            {
+                return Ok(Some(project.to_string()));
+            }
+        }
+
+        let path = dir.join(".tt.toml");
+        if path.is_file()
+            && let Some(project) = read_toml::<ProjectConfig>(&path)?.project
+        {
+            return Ok(Some(project));
+        }
+    }
+
+    let home = std::env::var("HOME").unwrap_or_default();
+    for directory in &config.directory {
+        let pattern = match directory.path.strip_prefix('~') {
+            Some(rest) => format!("{home}{rest}"),
+            None => directory.path.clone(),
+        };
+
+        let re = glob_to_regex(pattern.trim_end_matches('/'))?;
+        if dir.ancestors().any(|d| re.is_match(&d.to_string_lossy())) {
+            return Ok(Some(directory.project.clone()));
+        }
+    }
+
+    Ok(git_root(dir)
+        .and_then(|d| d.file_name())
+        .map(|name| name.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_project() {
+        let tmp = tempfile::tempdir().unwrap();
+        let src = tmp.path().join("clients/app-tt/src");
+        std::fs::create_dir_all(&src).unwrap();
+
+        let mut config = Config::default();
+        assert_eq!(detect_project_with_config(&src, &config).unwrap(), None);
+
+        std::fs::create_dir(tmp.path().join("clients/app-tt/.git")).unwrap();
+        let detected = detect_project_with_config(&src, &config).unwrap();
+        assert_eq!(detected.as_deref(), Some("app-tt"));
+
+        config.directory.push(DirectoryConfig {
+            path: format!("{}/clients/*", tmp.path().to_string_lossy()),
+            project: "clients".to_string(),
+        });
+        let detected = detect_project_with_config(&src, &config).unwrap();
+        assert_eq!(detected.as_deref(), Some("clients"));
+
+        std::fs::write(tmp.path().join("clients/.tt.toml"), "project = \"acme\"\n").unwrap();
+        let detected = detect_project_with_config(&src, &config).unwrap();
+        assert_eq!(detected.as_deref(), Some("acme"));
+
+        std::fs::write(src.join(".tt-project"), "backend\n").unwrap();
+        let detected = detect_project_with_config(&src, &config).unwrap();
+        assert_eq!(detected.as_deref(), Some("backend"));
+
+        std::fs::remove_file(src.join(".tt-project")).unwrap();
+        std::fs::write(src.join(".tt.toml"), "projects = \"typo\"\n").unwrap();
+        assert!(detect_project_with_config(&src, &config).is_err());
+    }
+}
@@ -1,10 +1,14 @@
+use crate::filter::Filter;
 use crate::styling::DASH;
-use crate::utils::{format_date, format_duration};
+use crate::utils::{format_date, format_duration, min_duration, to_naive_date_time};
 use anyhow::anyhow;
 use prettytable::{Table, row};
 use serde::{Deserialize, Serialize};
 use std::fs::DirEntry;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use tempfile::NamedTempFile;
 
 const RFC3339_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
 
@@ -21,9 +25,46 @@ struct FileEvent {
     stop: Option<String>,
     tags: Vec<String>,
     user: Option<String>,
+    // Written by "tt pause", and ignored by App::TimeTracker
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    breaks: Vec<Break>,
+    // Keys written by App::TimeTracker plugins, such as "rt" or "jira"
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Break {
+    pub start: chrono::NaiveDateTime,
+    pub stop: Option<chrono::NaiveDateTime>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FileProblem {
+    Unreadable(String),
+    InvalidStart(String),
+    InvalidStop(String),
+    StopBeforeStart,
+    StartMismatch,
+    ProjectMismatch,
+    StaleDuration,
+}
+
+impl std::fmt::Display for FileProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileProblem::Unreadable(e) => write!(f, "Unreadable: {e}"),
+            FileProblem::InvalidStart(s) => write!(f, "Invalid start \"{s}\""),
+            FileProblem::InvalidStop(s) => write!(f, "Invalid stop \"{s}\""),
+            FileProblem::StopBeforeStart => write!(f, "Stop is before start"),
+            FileProblem::StartMismatch => write!(f, "File name does not match start time"),
+            FileProblem::ProjectMismatch => write!(f, "File name does not match project"),
+            FileProblem::StaleDuration => write!(f, "Stale duration or seconds"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct TimeEvent {
     pub description: String,
     pub project: String,
@@ -31,10 +72,41 @@ pub struct TimeEvent {
     pub start: chrono::NaiveDateTime,
     pub stop: Option<chrono::NaiveDateTime>,
     pub tags: Vec<String>,
+    pub user: Option<String>,
+    pub breaks: Vec<Break>,
+    pub extra: serde_json::Map<String, serde_json::Value>,
+    /// The file the event was read from, which can differ from `path()`, such as
+    /// for a .json file or a file that was renamed by hand
+    pub source: Option<PathBuf>,
+}
+
+// Parses the file strictly, for input from the user, instead of falling back to defaults
+impl TryFrom<FileEvent> for TimeEvent {
+    type Error = anyhow::Error;
+
+    fn try_from(value: FileEvent) -> Result<Self, Self::Error> {
+        if chrono::NaiveDateTime::parse_from_str(&value.start, RFC3339_FORMAT).is_err() {
+            return Err(anyhow!(
+                "Invalid start \"{}\", expected YYYY-MM-DDTHH:MM:SS",
+                value.start
+            ));
+        }
+
+        if let Some(stop) = &value.stop
+            && chrono::NaiveDateTime::parse_from_str(stop, RFC3339_FORMAT).is_err()
+        {
+            return Err(anyhow!(
+                "Invalid stop \"{stop}\", expected YYYY-MM-DDTHH:MM:SS or null"
+            ));
+        }
+
+        Ok(TimeEvent::from_file_event(value))
+    }
 }
 
-impl From<FileEvent> for TimeEvent {
-    fn from(value: FileEvent) -> Self {
+impl TimeEvent {
+    // Falls back to defaults, so odd files written by other tools can still be read
+    fn from_file_event(value: FileEvent) -> Self {
         let start = chrono::NaiveDateTime::parse_from_str(&value.start, RFC3339_FORMAT)
             .unwrap_or_else(|_| chrono::Local::now().naive_local());
 
@@ -49,21 +121,23 @@ impl From<FileEvent> for TimeEvent {
             start,
             stop,
             tags: value.tags,
+            user: value.user,
+            breaks: value.breaks,
+            extra: value.extra,
+            source: None,
         }
     }
-}
 
-impl TimeEvent {
     pub fn add_tags<T: ToString>(&mut self, tags: Vec<T>) {
-        self.tags.extend(tags.into_iter().map(|t| t.to_string()));
-        self.tags = std::collections::HashSet::<&String>::from_iter(self.tags.iter())
-            .iter()
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        for tag in tags.into_iter().map(|t| t.to_string()) {
+            if !self.has_tag(&tag) {
+                self.tags.push(tag);
+            }
+        }
     }
 
     pub fn delete(&self) -> Result<(), anyhow::Error> {
-        Ok(std::fs::remove_file(self.path())?)
+        Ok(std::fs::remove_file(self.source_path())?)
     }
 
     pub fn description(&self) -> &str {
@@ -74,25 +148,214 @@ impl TimeEvent {
         }
     }
 
+    /// The time between start and stop, without the breaks
     pub fn duration(&self) -> chrono::Duration {
-        self.stop
-            .unwrap_or_else(|| chrono::Local::now().naive_local())
-            - self.start
+        let stop = self
+            .stop
+            .unwrap_or_else(|| chrono::Local::now().naive_local());
+
+        // A break without a stop time lasts until the event is stopped
+        let mut breaks = self
+            .breaks
+            .iter()
+            .map(|b| (b.start.max(self.start), b.stop.unwrap_or(stop).min(stop)))
+            .filter(|(start, stop)| start < stop)
+            .collect::<Vec<_>>();
+        breaks.sort();
+
+        // Overlapping breaks are only subtracted once
+        let mut duration = stop - self.start;
+        let mut counted_until = self.start;
+        for (start, stop) in breaks {
+            if stop > counted_until {
+                duration -= stop - start.max(counted_until);
+                counted_until = stop;
+            }
+        }
+
+        duration
+    }
+
+    pub fn duration_as_hms(&self) -> String {
+        let duration = self.duration();
+        format!(
+            "{:02}:{:02}:{:02}",
+            duration.num_hours(),
+            duration.num_minutes() % 60,
+            duration.num_seconds() % 60
+        )
+    }
+
+    /// A short id that is stable as long as the start time and project is unchanged
+    pub fn id(&self) -> String {
+        hash_id(&format!(
+            "{}_{}",
+            self.start.format("%Y%m%d-%H%M%S"),
+            self.project
+        ))
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.stop.is_none() && self.breaks.last().is_some_and(|b| b.stop.is_none())
+    }
+
+    pub fn pause(&mut self, at: chrono::NaiveDateTime) -> Result<(), anyhow::Error> {
+        if self.stop.is_some() {
+            return Err(anyhow!("The event is not running"));
+        }
+        if self.is_paused() {
+            return Err(anyhow!("The event is already paused"));
+        }
+
+        let last = self
+            .breaks
+            .last()
+            .and_then(|b| b.stop)
+            .unwrap_or(self.start);
+        if at < last {
+            return Err(anyhow!(
+                "Cannot pause before {}",
+                format_date(&last, "full")
+            ));
+        }
+
+        self.breaks.push(Break {
+            start: at,
+            stop: None,
+        });
+
+        Ok(())
+    }
+
+    pub fn unpause(&mut self, at: chrono::NaiveDateTime) -> Result<(), anyhow::Error> {
+        let Some(current) = self.breaks.last_mut().filter(|b| b.stop.is_none()) else {
+            return Err(anyhow!("The event is not paused"));
+        };
+
+        if at < current.start {
+            return Err(anyhow!(
+                "Cannot unpause before {}",
+                format_date(&current.start, "full")
+            ));
+        }
+
+        current.stop = Some(at);
+        Ok(())
+    }
+
+    /// Stops the event and ends the open break. The event is saved, or deleted if it
+    /// is shorter than TT_MIN_DURATION. Returns false if the event was deleted.
+    pub fn stop_at(&mut self, stop: chrono::NaiveDateTime) -> Result<bool, anyhow::Error> {
+        if stop < self.start {
+            return Err(anyhow!("The stop time cannot be before the start time"));
+        }
+
+        // A break that starts after the stop time is not part of the event
+        self.breaks.retain(|b| b.start < stop);
+        if self.is_paused() {
+            self.unpause(stop)?;
+        }
+
+        self.stop = Some(stop);
+        if self.duration().num_seconds() < min_duration()? {
+            self.delete()?;
+            return Ok(false);
+        }
+
+        self.save()?;
+        Ok(true)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Parses an event edited by the user, so an invalid start or stop is an error
+    pub fn from_json(value: serde_json::Value) -> Result<TimeEvent, anyhow::Error> {
+        serde_json::from_value::<FileEvent>(value)?.try_into()
+    }
+
+    /// Parses a line written by `to_line()`. The fields that are not part of the line,
+    /// such as "user", are taken from the original event with the same id.
+    pub fn from_line(
+        line: &str,
+        originals: &[TimeEvent],
+    ) -> Result<(Option<String>, TimeEvent), anyhow::Error> {
+        let invalid = || anyhow!("Invalid line \"{line}\"");
+        let mut tokens = line.split_whitespace().peekable();
+
+        let id = tokens
+            .next_if(|t| t.len() == 8 && t.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(|t| t.to_string());
+
+        let mut event = originals
+            .iter()
+            .find(|e| Some(e.id()) == id)
+            .cloned()
+            .unwrap_or_default();
+
+        let start = format!(
+            "{} {}",
+            tokens.next().ok_or_else(invalid)?,
+            tokens.next().ok_or_else(invalid)?
+        );
+        event.start = to_naive_date_time(Some(&start), None)?;
+
+        if tokens.next() != Some("-") {
+            return Err(invalid());
+        }
+
+        event.stop = match tokens.next().ok_or_else(invalid)? {
+            "?" => None,
+            date if date.contains('-') => {
+                let stop = format!("{} {}", date, tokens.next().ok_or_else(invalid)?);
+                Some(to_naive_date_time(Some(&stop), None)?)
+            }
+            time => Some(to_naive_date_time(
+                Some(&time.to_string()),
+                Some(&event.start),
+            )?),
+        };
+
+        event.project = tokens.next().ok_or_else(invalid)?.to_string();
+        event.tags = vec![];
+        while let Some(tag) = tokens.next_if(|t| t.starts_with('+')) {
+            event.add_tags(vec![&tag[1..]]);
+        }
+
+        event.description = match tokens.peek() {
+            Some(quoted) if quoted.starts_with('"') => {
+                // The tokens are slices of the line, so the offset is where the quote starts
+                let offset = quoted.as_ptr() as usize - line.as_ptr() as usize;
+                serde_json::from_str::<String>(line[offset..].trim_end()).map_err(|_| invalid())?
+            }
+            _ => tokens.collect::<Vec<&str>>().join(" "),
+        };
+
+        Ok((id, event))
     }
 
     pub fn from_string(content: &str) -> Result<TimeEvent, anyhow::Error> {
-        Ok(serde_json::from_str::<FileEvent>(content)?.into())
+        Ok(TimeEvent::from_file_event(
+            serde_json::from_str::<FileEvent>(content)?,
+        ))
     }
 
     pub fn matches_args(&self, args: &clap::ArgMatches) -> bool {
-        if let Some(project) = args.get_one::<String>("project")
+        if let Ok(Some(project)) = args.try_get_one::<String>("project")
             && self.project != *project
         {
             return false;
         }
 
-        if let Some(tag) = args.get_one::<String>("tag")
-            && !self.tags.iter().any(|t| t == tag)
+        if let Ok(Some(tag)) = args.try_get_one::<String>("tag")
+            && !self.has_tag(tag)
+        {
+            return false;
+        }
+
+        if let Ok(Some(filter)) = args.try_get_one::<Filter>("filter")
+            && !filter.matches(self)
         {
             return false;
         }
@@ -100,22 +363,63 @@ impl TimeEvent {
         true
     }
 
-    pub fn path(&self) -> std::path::PathBuf {
-        tracker_dir().join(
-            format!(
-                "{}_{}.trc",
-                self.start.format("%Y/%m/%Y%m%d-%H%M%S"),
-                self.project,
-            )
-            .parse::<std::path::PathBuf>()
-            .unwrap(),
+    /// Saves the event to `path()` and removes the file it was read from. The old
+    /// file is renamed before the event is written, so nothing is left behind if
+    /// the file cannot be moved.
+    pub fn move_from(&self, old_path: &Path) -> Result<(), anyhow::Error> {
+        validate_project(&self.project)?;
+        let path = self.path();
+        let dir = path.parent().expect("Invalid path: {path}");
+        std::fs::create_dir_all(dir)?;
+
+        if path != old_path && old_path.exists() {
+            if path.exists() {
+                return Err(anyhow!(
+                    "Unable to move {old_path:?}, since {path:?} exists"
+                ));
+            }
+
+            std::fs::rename(old_path, &path)?;
+        }
+
+        // Write to a temp file first, so the event is replaced atomically
+        let mut tmp = NamedTempFile::new_in(dir)?;
+        tmp.write_all(self.serialize(false)?.as_bytes())?;
+        tmp.persist(&path)?;
+        Ok(())
+    }
+
+    pub fn overlaps(&self, other: &TimeEvent) -> bool {
+        let now = chrono::Local::now().naive_local();
+        self.start < other.stop.unwrap_or(now) && other.start < self.stop.unwrap_or(now)
+    }
+
+    pub fn path(&self) -> PathBuf {
+        tracker_dir().join(self.relative_path())
+    }
+
+    pub fn source_path(&self) -> PathBuf {
+        self.source.clone().unwrap_or_else(|| self.path())
+    }
+
+    // The path inside ~/.TimeTracker, such as "2025/09/20250907-205248_app-tt.trc"
+    fn relative_path(&self) -> PathBuf {
+        format!(
+            "{}_{}.trc",
+            self.start.format("%Y/%m/%Y%m%d-%H%M%S"),
+            self.project,
         )
+        .parse::<PathBuf>()
+        .unwrap()
     }
 
     pub fn tags_as_string(&self) -> String {
-        let tags = std::collections::HashSet::<&String>::from_iter(self.tags.iter())
+        let mut seen = std::collections::HashSet::new();
+        let tags = self
+            .tags
             .iter()
-            .map(|s| s.to_string())
+            .filter(|t| seen.insert(*t))
+            .cloned()
             .collect::<Vec<String>>()
             .join(",");
 
@@ -126,36 +430,93 @@ impl TimeEvent {
         }
     }
 
+    pub fn remove_tags<T: AsRef<str>>(&mut self, tags: &[T]) {
+        self.tags.retain(|t| !tags.iter().any(|r| r.as_ref() == t));
+    }
+
+    /// Saves the event to `path()`, and moves the file it was read from, if the
+    /// start time or project was changed
     pub fn save(&self) -> Result<(), anyhow::Error> {
-        let path = self.path();
-        std::fs::create_dir_all(path.parent().expect("Invalid path: {path}"))?;
-        Ok(std::fs::write(&path, &self.serialize(false)?)?)
+        self.move_from(&self.source_path())
     }
 
     pub fn serialize(&self, pretty: bool) -> Result<String, anyhow::Error> {
-        let duration = self.duration();
-        let file_event = FileEvent {
+        let file_event = self.to_file_event();
+        if pretty {
+            Ok(serde_json::to_string_pretty(&file_event)?)
+        } else {
+            Ok(serde_json::to_string(&file_event)?)
+        }
+    }
+
+    fn to_file_event(&self) -> FileEvent {
+        FileEvent {
             class: Some("App::TimeTracker::Data::Task".to_string()),
             description: Some(self.description.clone()),
-            duration: Some(format!(
-                "{:02}:{:02}:{:02}",
-                duration.num_hours(),
-                duration.num_minutes() % 60,
-                duration.num_seconds() % 60
-            )),
+            duration: Some(self.duration_as_hms()),
             project: self.project.clone(),
             seconds: Some(self.duration().num_seconds()),
             start: self.start.format(RFC3339_FORMAT).to_string(),
             stop: self.stop.map(|s| s.format(RFC3339_FORMAT).to_string()),
             tags: self.tags.clone(),
-            user: std::env::var("USER").ok(),
+            user: self.user.clone().or_else(|| std::env::var("USER").ok()),
+            breaks: self.breaks.clone(),
+            extra: self.extra.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<serde_json::Value, anyhow::Error> {
+        Ok(serde_json::to_value(self.to_file_event())?)
+    }
+
+    /// Formats the event as "id start - stop project +tag description"
+    pub fn to_line(&self) -> String {
+        // Seconds are only included when needed, since they are part of the file name
+        fn format_time(d: &chrono::NaiveDateTime) -> String {
+            if d.format("%S").to_string() == "00" {
+                d.format("%H:%M").to_string()
+            } else {
+                d.format("%H:%M:%S").to_string()
+            }
+        }
+
+        let stop = match self.stop {
+            Some(stop) if stop.date() == self.start.date() => format_time(&stop),
+            Some(stop) => format!("{} {}", format_date(&stop, "ymd"), format_time(&stop)),
+            None => "?".to_string(),
         };
 
-        if pretty {
-            Ok(serde_json::to_string_pretty(&file_event)?)
-        } else {
-            Ok(serde_json::to_string(&file_event)?)
+        let mut line = format!(
+            "{}  {} {} - {}  {}",
+            self.id(),
+            format_date(&self.start, "ymd"),
+            format_time(&self.start),
+            stop,
+            self.project
+        );
+
+        if !self.tags.is_empty() {
+            line.push_str("  +");
+            line.push_str(&self.tags.join(" +"));
         }
+
+        // The description is quoted if it would not be parsed back as the same text
+        let description = &self.description;
+        if description.starts_with(['+', '"'])
+            || description
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+                != *description
+        {
+            line.push_str("  ");
+            line.push_str(&serde_json::Value::from(description.as_str()).to_string());
+        } else if !description.is_empty() {
+            line.push_str("  ");
+            line.push_str(description);
+        }
+
+        line
     }
 
     pub fn to_table(&self, status: &str) -> Table {
@@ -174,10 +535,102 @@ impl TimeEvent {
         t.add_row(row!["Tags", self.tags_as_string()]);
         t.add_row(row!["Description", self.description()]);
         t.add_row(row!["File", self.path().to_string_lossy()]);
+        t.add_row(row!["Id", self.id()]);
         t
     }
 }
 
+// FNV-1a, since DefaultHasher is not guaranteed to be stable between releases
+fn hash_id(key: &str) -> String {
+    let hash = key.bytes().fold(0xcbf29ce484222325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    });
+
+    format!("{:08x}", hash >> 32)
+}
+
+/// The id of the event in a file, since the file name is "YYYYmmdd-HHMMSS_project.trc"
+pub fn path_id(path: &Path) -> String {
+    hash_id(&path.file_stem().unwrap_or_default().to_string_lossy())
+}
+
+/// The project name is part of the file name, so it cannot be empty or contain "/"
+pub fn validate_project(project: &str) -> Result<(), anyhow::Error> {
+    if project.is_empty() || project.contains('/') {
+        return Err(anyhow!("Invalid project name \"{project}\""));
+    }
+
+    Ok(())
+}
+
+/// Validates a single file, without falling back to defaults like `from_string()` does
+pub fn check_file(path: &Path) -> (Option<TimeEvent>, Vec<FileProblem>) {
+    let file_event = match std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| Ok(serde_json::from_str::<FileEvent>(&content)?))
+    {
+        Ok(file_event) => file_event,
+        Err(e) => return (None, vec![FileProblem::Unreadable(e.to_string())]),
+    };
+
+    let mut problems = vec![];
+    let file_stem = path
+        .file_stem()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let start = chrono::NaiveDateTime::parse_from_str(&file_event.start, RFC3339_FORMAT);
+    if start.is_err() {
+        problems.push(FileProblem::InvalidStart(file_event.start.clone()));
+    }
+
+    if let Some(stop) = &file_event.stop
+        && chrono::NaiveDateTime::parse_from_str(stop, RFC3339_FORMAT).is_err()
+    {
+        problems.push(FileProblem::InvalidStop(stop.clone()));
+    }
+
+    let seconds = file_event.seconds;
+    let duration = file_event.duration.clone();
+    let mut event = TimeEvent::from_file_event(file_event);
+    event.source = Some(path.to_path_buf());
+    if let Ok(start) = start {
+        let expected = event.relative_path();
+        if !path.ends_with(&expected) {
+            if !file_stem.starts_with(&start.format("%Y%m%d-%H%M%S_").to_string())
+                || !path
+                    .parent()
+                    .is_some_and(|dir| dir.ends_with(expected.parent().unwrap_or(&expected)))
+            {
+                problems.push(FileProblem::StartMismatch);
+            }
+            if file_stem.get(16..) != Some(event.project.as_str()) {
+                problems.push(FileProblem::ProjectMismatch);
+            }
+        }
+    } else if let Some(start) = file_stem
+        .get(0..15)
+        .and_then(|s| chrono::NaiveDateTime::parse_from_str(s, "%Y%m%d-%H%M%S").ok())
+    {
+        // Recover the start time from the file name, so the event can be repaired
+        event.start = start;
+    } else {
+        return (None, problems);
+    }
+
+    if let Some(stop) = event.stop {
+        if stop < event.start {
+            problems.push(FileProblem::StopBeforeStart);
+        } else if seconds != Some(event.duration().num_seconds())
+            || duration != Some(event.duration_as_hms())
+        {
+            problems.push(FileProblem::StaleDuration);
+        }
+    }
+
+    (Some(event), problems)
+}
+
 fn file_in_date_range(
     file: &DirEntry,
     since: &chrono::NaiveDate,
@@ -227,8 +680,9 @@ pub fn find_last_event() -> Result<TimeEvent, anyhow::Error> {
                     continue;
                 }
 
-                let content = std::fs::read_to_string(file.path())?;
-                if let Ok(event) = TimeEvent::from_string(&content) {
+                let content = std::fs::read_to_string(&path)?;
+                if let Ok(mut event) = TimeEvent::from_string(&content) {
+                    event.source = Some(path);
                     return Ok(event);
                 }
             }
@@ -238,6 +692,81 @@ pub fn find_last_event() -> Result<TimeEvent, anyhow::Error> {
     Err(anyhow!("Unable to find the last tracked event"))
 }
 
+// Finds the event with an id that starts with the given prefix
+fn find_event_by_id(events: Vec<TimeEvent>, id: &str) -> Result<Option<TimeEvent>, anyhow::Error> {
+    let prefix = id.to_lowercase();
+    let mut found = events
+        .into_iter()
+        .filter(|e| e.id().starts_with(&prefix))
+        .collect::<Vec<TimeEvent>>();
+
+    match found.len() {
+        0 | 1 => Ok(found.pop()),
+        _ => Err(anyhow!("The id \"{id}\" matches more than one event")),
+    }
+}
+
+/// Finds a single event by `id()` (or a prefix of it) or start time
+pub fn find_event(id: &str) -> Result<TimeEvent, anyhow::Error> {
+    let id = id.trim().to_string();
+    if id.len() >= 4 && id.len() <= 8 && id.chars().all(|c| c.is_ascii_hexdigit()) {
+        let events = find_events(&chrono::NaiveDate::MIN, &chrono::NaiveDate::MAX);
+        if let Some(event) = find_event_by_id(events, &id)? {
+            return Ok(event);
+        }
+    }
+
+    let start = to_naive_date_time(Some(&id), None)
+        .map_err(|_| anyhow!("Unable to find event with id or start time \"{id}\""))?;
+
+    let mut found = find_events(&start.date(), &start.date())
+        .into_iter()
+        .filter(|e| e.start == start)
+        .collect::<Vec<TimeEvent>>();
+
+    match found.len() {
+        1 => Ok(found.remove(0)),
+        0 => Err(anyhow!(
+            "Unable to find event with id or start time \"{id}\""
+        )),
+        _ => Err(anyhow!(
+            "More than one event starts at \"{id}\", use the id instead"
+        )),
+    }
+}
+
+pub fn find_files() -> Vec<PathBuf> {
+    let mut files = vec![];
+    for year_dir in read_dir(tracker_dir()) {
+        for month_dir in read_dir(year_dir.path()) {
+            for file in read_dir(month_dir.path()) {
+                let path = file.path();
+                let ext = path.extension().and_then(|s| s.to_str());
+                if ext == Some("trc") || ext == Some("json") {
+                    files.push(path);
+                }
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+/// Finds the running events, oldest first. Only the last event and the events
+/// started within a month before it are considered, to avoid reading every file.
+pub fn find_running_events() -> Vec<TimeEvent> {
+    let Ok(last) = find_last_event() else {
+        return vec![];
+    };
+
+    let since = (last.start - chrono::Duration::days(31)).date();
+    find_events(&since, &last.start.date())
+        .into_iter()
+        .filter(|e| e.stop.is_none())
+        .collect()
+}
+
 pub fn find_events(since: &chrono::NaiveDate, until: &chrono::NaiveDate) -> Vec<TimeEvent> {
     let mut events = vec![];
     for year_dir in read_dir(tracker_dir()) {
@@ -253,9 +782,10 @@ pub fn find_events(since: &chrono::NaiveDate, until: &chrono::NaiveDate) -> Vec<
                     continue;
                 }
 
-                if let Ok(content) = std::fs::read_to_string(file.path())
-                    && let Ok(event) = TimeEvent::from_string(&content)
+                if let Ok(content) = std::fs::read_to_string(&path)
+                    && let Ok(mut event) = TimeEvent::from_string(&content)
                 {
+                    event.source = Some(path);
                     events.push(event);
                 }
             }
@@ -266,15 +796,317 @@ pub fn find_events(since: &chrono::NaiveDate, until: &chrono::NaiveDate) -> Vec<
     events
 }
 
-fn read_dir(path: impl AsRef<std::path::Path>) -> Vec<DirEntry> {
+fn read_dir(path: impl AsRef<Path>) -> Vec<DirEntry> {
     std::fs::read_dir(path)
         .map(|rd| rd.filter_map(Result::ok).collect())
         .unwrap_or_default()
 }
 
-fn tracker_dir() -> std::path::PathBuf {
+// Stored outside of the year directories, so it is not mistaken for an event
+fn activity_path() -> PathBuf {
+    tracker_dir().join(".last-activity")
+}
+
+/// Finds the last time activity was recorded by "tt ping"
+pub fn find_last_activity() -> Option<chrono::NaiveDateTime> {
+    let content = std::fs::read_to_string(activity_path()).ok()?;
+    chrono::NaiveDateTime::parse_from_str(content.trim(), RFC3339_FORMAT).ok()
+}
+
+pub fn save_activity(at: &chrono::NaiveDateTime) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(tracker_dir())?;
+    Ok(std::fs::write(
+        activity_path(),
+        format!("{}\n", at.format(RFC3339_FORMAT)),
+    )?)
+}
+
+fn tracker_dir() -> PathBuf {
     let home = std::env::var("HOME").expect("Can't find ~/.TimeTracker, without  being set");
-    format!("{}/.TimeTracker", home)
-        .parse::<std::path::PathBuf>()
-        .unwrap()
+    format!("{}/.TimeTracker", home).parse::<PathBuf>().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_keeps_unknown_fields() {
+        let content = r#"{
+            "__CLASS__": "App::TimeTracker::Data::Task",
+            "project": "app-tt",
+            "start": "2025-09-07T20:52:48",
+            "stop": "2025-09-07T21:12:48",
+            "tags": ["rust"],
+            "user": "perl-user",
+            "jira": "ABC-123",
+            "rt": {"id": 42}
+        }"#;
+
+        let event = TimeEvent::from_string(content).unwrap();
+        assert_eq!(event.user.as_deref(), Some("perl-user"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&event.serialize(false).unwrap()).unwrap();
+        assert_eq!(json["user"], "perl-user");
+        assert_eq!(json["jira"], "ABC-123");
+        assert_eq!(json["rt"]["id"], 42);
+        assert_eq!(json["seconds"], 1200);
+        assert_eq!(json["duration"], "00:20:00");
+    }
+
+    #[test]
+    fn test_from_json_is_strict() {
+        let json = serde_json::json!({
+            "project": "app-tt",
+            "start": "2025-09-07T20:52:48",
+            "stop": "2025-09-07 21:12",
+            "tags": [],
+        });
+
+        // A file can be read, but an edited event must have valid dates
+        let event = TimeEvent::from_string(&json.to_string()).unwrap();
+        assert_eq!(event.stop, None);
+        let err = TimeEvent::from_json(json.clone()).unwrap_err().to_string();
+        assert!(
+            err.starts_with("Invalid stop \"2025-09-07 21:12\""),
+            "{err}"
+        );
+
+        let mut json = json;
+        json["stop"] = serde_json::Value::Null;
+        assert!(TimeEvent::from_json(json.clone()).is_ok());
+        json["start"] = "20:52".into();
+        let err = TimeEvent::from_json(json).unwrap_err().to_string();
+        assert!(err.starts_with("Invalid start \"20:52\""), "{err}");
+    }
+
+    #[test]
+    fn test_line_format() {
+        let original = TimeEvent {
+            description: "Convert from perl to rust".to_string(),
+            project: "app-tt".to_string(),
+            start: "2025-09-07T20:52:48".parse().unwrap(),
+            stop: Some("2025-09-07T21:30:00".parse().unwrap()),
+            tags: vec!["rust".to_string(), "cli".to_string()],
+            user: Some("perl-user".to_string()),
+            ..TimeEvent::default()
+        };
+
+        let line = original.to_line();
+        assert_eq!(
+            line,
+            format!(
+                "{}  2025-09-07 20:52:48 - 21:30  app-tt  +rust +cli  Convert from perl to rust",
+                original.id()
+            )
+        );
+
+        let originals = vec![original];
+        let (id, event) = TimeEvent::from_line(&line, &originals).unwrap();
+        assert_eq!(id, Some(originals[0].id()));
+        assert_eq!(event.to_line(), line);
+        assert_eq!(event.user.as_deref(), Some("perl-user"));
+
+        let (id, event) =
+            TimeEvent::from_line("2025-09-08 23:00 - 2025-09-09 01:00  work", &originals).unwrap();
+        assert_eq!(id, None);
+        assert_eq!(event.duration().num_minutes(), 120);
+        assert_eq!(event.user, None);
+        assert!(event.tags.is_empty());
+
+        let (_, event) = TimeEvent::from_line("2025-09-08 08:00 - ?  work +a", &[]).unwrap();
+        assert_eq!(event.stop, None);
+        assert_eq!(event.tags, vec!["a".to_string()]);
+
+        // Descriptions that would not be parsed as the same text are quoted
+        for description in [
+            "+1 looks  good",
+            " padded ",
+            "two\nlines",
+            "\"quoted\" text",
+        ] {
+            let event = TimeEvent {
+                description: description.to_string(),
+                ..originals[0].clone()
+            };
+
+            let line = event.to_line();
+            assert!(!line.contains('\n'), "{line}");
+            let (_, parsed) = TimeEvent::from_line(&line, &originals).unwrap();
+            assert_eq!(parsed.description, description);
+            assert_eq!(parsed.tags, originals[0].tags);
+            assert_eq!(parsed.to_line(), line);
+        }
+
+        let (_, event) =
+            TimeEvent::from_line("2025-09-08 08:00 - 09:00  work  a  b", &originals).unwrap();
+        assert_eq!(event.description, "a b");
+
+        for line in [
+            "",
+            "2025-09-08 08:00",
+            "2025-09-08 08:00 12:00 work",
+            "2025-09-08 08:00 - 12:00  work  \"unterminated",
+        ] {
+            assert!(TimeEvent::from_line(line, &[]).is_err(), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_duration_with_breaks() {
+        let mut event = TimeEvent {
+            project: "app-tt".to_string(),
+            start: "2025-09-08T08:00:00".parse().unwrap(),
+            ..TimeEvent::default()
+        };
+
+        event.pause("2025-09-08T11:30:00".parse().unwrap()).unwrap();
+        assert!(event.is_paused());
+        assert!(event.pause("2025-09-08T11:45:00".parse().unwrap()).is_err());
+        event
+            .unpause("2025-09-08T12:00:00".parse().unwrap())
+            .unwrap();
+        assert!(!event.is_paused());
+
+        // Breaks outside the event, or overlapping other breaks, are only counted once
+        event.breaks.push(Break {
+            start: "2025-09-08T11:45:00".parse().unwrap(),
+            stop: Some("2025-09-08T12:15:00".parse().unwrap()),
+        });
+        event.breaks.push(Break {
+            start: "2025-09-08T16:00:00".parse().unwrap(),
+            stop: None,
+        });
+
+        event.stop = Some("2025-09-08T16:30:00".parse().unwrap());
+        assert_eq!(event.duration_as_hms(), "07:15:00");
+
+        let json = event.to_json().unwrap();
+        assert_eq!(json["breaks"][0]["start"], "2025-09-08T11:30:00");
+        assert_eq!(json["breaks"][2]["stop"], serde_json::Value::Null);
+        assert_eq!(
+            TimeEvent::from_json(json).unwrap().duration(),
+            event.duration()
+        );
+    }
+
+    #[test]
+    fn test_check_file() {
+        let tmp = tempfile::tempdir().unwrap();
+        let event = TimeEvent {
+            project: "app-tt".to_string(),
+            start: "2025-09-07T08:00:00".parse().unwrap(),
+            stop: Some("2025-09-07T09:30:00".parse().unwrap()),
+            ..TimeEvent::default()
+        };
+
+        let write = |name: &str, edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut value = event.to_json().unwrap();
+            edit(&mut value);
+            let path = tmp.path().join("2025/09").join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, value.to_string()).unwrap();
+            path
+        };
+
+        let valid = write("20250907-080000_app-tt.trc", &|_| {});
+        let (checked, problems) = check_file(&valid);
+        assert_eq!(checked.unwrap().start, event.start);
+        assert_eq!(problems, vec![]);
+
+        let path = write("20250907-080000_app-tt.json", &|_| {});
+        assert_eq!(check_file(&path).1, vec![]);
+
+        std::fs::write(&valid, "{").unwrap();
+        let (checked, problems) = check_file(&valid);
+        assert!(checked.is_none());
+        assert!(matches!(problems[..], [FileProblem::Unreadable(_)]));
+
+        // The start time is recovered from the file name
+        let path = write("20250907-080000_app-tt.trc", &|v| {
+            v["start"] = "08:00".into()
+        });
+        let (checked, problems) = check_file(&path);
+        assert_eq!(checked.unwrap().start, event.start);
+        assert_eq!(
+            problems,
+            vec![FileProblem::InvalidStart("08:00".to_string())]
+        );
+
+        let path = write("notes.trc", &|v| v["start"] = "08:00".into());
+        let (checked, problems) = check_file(&path);
+        assert!(checked.is_none());
+        assert_eq!(
+            problems,
+            vec![FileProblem::InvalidStart("08:00".to_string())]
+        );
+
+        let path = write("20250907-080000_app-tt.trc", &|v| {
+            v["stop"] = "09:30".into()
+        });
+        let problems = check_file(&path).1;
+        assert_eq!(
+            problems,
+            vec![FileProblem::InvalidStop("09:30".to_string())]
+        );
+
+        let path = write("20250907-080000_app-tt.trc", &|v| {
+            v["stop"] = "2025-09-07T07:00:00".into()
+        });
+        assert_eq!(check_file(&path).1, vec![FileProblem::StopBeforeStart]);
+
+        let path = write("20250907-080000_app-tt.trc", &|v| v["seconds"] = 60.into());
+        assert_eq!(check_file(&path).1, vec![FileProblem::StaleDuration]);
+
+        let path = write("20250907-090000_app-tt.trc", &|_| {});
+        assert_eq!(check_file(&path).1, vec![FileProblem::StartMismatch]);
+
+        let path = write("20250907-080000_other.trc", &|_| {});
+        assert_eq!(check_file(&path).1, vec![FileProblem::ProjectMismatch]);
+
+        let path = tmp.path().join("2025/10/20250907-080000_app-tt.trc");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::copy(write("20250907-080000_app-tt.trc", &|_| {}), &path).unwrap();
+        assert_eq!(check_file(&path).1, vec![FileProblem::StartMismatch]);
+    }
+
+    #[test]
+    fn test_id() {
+        let event = |start: &str, project: &str| TimeEvent {
+            project: project.to_string(),
+            start: start.parse().unwrap(),
+            ..TimeEvent::default()
+        };
+
+        // The ids are used in scripts, so they must never change
+        let original = event("2025-09-07T20:52:48", "app-tt");
+        assert_eq!(original.id(), "74ff598b");
+        assert_eq!(
+            TimeEvent {
+                description: "Changed".to_string(),
+                stop: Some("2025-09-07T21:00:00".parse().unwrap()),
+                ..original.clone()
+            }
+            .id(),
+            "74ff598b"
+        );
+        assert_eq!(path_id(&original.relative_path()), "74ff598b");
+        assert_ne!(event("2025-09-07T20:52:48", "work").id(), "74ff598b");
+
+        let events = vec![
+            original,
+            event("2025-09-08T10:07:00", "work"),
+            event("2025-09-08T13:27:00", "work"),
+        ];
+        assert_eq!(events[1].id(), "2abea168");
+        assert_eq!(events[2].id(), "2abedcdf");
+
+        let found = |id: &str| find_event_by_id(events.clone(), id);
+        assert_eq!(found("74ff").unwrap().unwrap().project, "app-tt");
+        assert_eq!(found("74FF598B").unwrap().unwrap().project, "app-tt");
+        assert_eq!(found("2abed").unwrap().unwrap().id(), "2abedcdf");
+        assert!(found("2abe").is_err());
+        assert!(found("0000").unwrap().is_none());
+    }
 }
@@ -0,0 +1,414 @@
+use crate::event::TimeEvent;
+use anyhow::anyhow;
+use regex::Regex;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Match,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Op {
+    fn is_true(&self, ord: Ordering) -> bool {
+        match self {
+            Op::Eq => ord == Ordering::Equal,
+            Op::Ne => ord != Ordering::Equal,
+            Op::Lt => ord == Ordering::Less,
+            Op::Le => ord != Ordering::Greater,
+            Op::Gt => ord == Ordering::Greater,
+            Op::Ge => ord != Ordering::Less,
+            Op::Match => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TextField {
+    Description,
+    Project,
+    Tag,
+}
+
+#[derive(Clone, Debug)]
+enum Condition {
+    Text(TextField, Op, Vec<Regex>),
+    Duration(Op, chrono::Duration),
+    Weekday(Op, Vec<chrono::Weekday>),
+    Time(Op, chrono::NaiveTime),
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Condition(Condition),
+}
+
+impl Expr {
+    fn matches(&self, event: &TimeEvent) -> bool {
+        match self {
+            Expr::And(a, b) => a.matches(event) && b.matches(event),
+            Expr::Or(a, b) => a.matches(event) || b.matches(event),
+            Expr::Not(a) => !a.matches(event),
+            Expr::Condition(c) => c.matches(event),
+        }
+    }
+}
+
+/// A parsed filter expression, such as "project=work,home and not tag=meeting"
+#[derive(Clone, Debug)]
+pub struct Filter(Expr);
+
+impl Filter {
+    pub fn matches(&self, event: &TimeEvent) -> bool {
+        self.0.matches(event)
+    }
+}
+
+impl Condition {
+    fn matches(&self, event: &TimeEvent) -> bool {
+        match self {
+            Condition::Text(field, op, patterns) => {
+                let values = match field {
+                    TextField::Description => vec![&event.description],
+                    TextField::Project => vec![&event.project],
+                    TextField::Tag => event.tags.iter().collect(),
+                };
+
+                let found = values
+                    .iter()
+                    .any(|v| patterns.iter().any(|p| p.is_match(v)));
+
+                if *op == Op::Ne { !found } else { found }
+            }
+            Condition::Duration(op, duration) => op.is_true(event.duration().cmp(duration)),
+            Condition::Weekday(op, weekdays) => {
+                let found = weekdays.contains(&chrono::Datelike::weekday(&event.start));
+                if *op == Op::Ne { !found } else { found }
+            }
+            Condition::Time(op, time) => op.is_true(event.start.time().cmp(time)),
+        }
+    }
+}
+
+pub fn glob_to_regex(glob: &str) -> Result<Regex, anyhow::Error> {
+    let mut re = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => re.push_str(".*"),
+            '?' => re.push('.'),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+
+    re.push('$');
+    Ok(Regex::new(&re)?)
+}
+
+fn parse_duration(s: &str) -> Result<chrono::Duration, anyhow::Error> {
+    let mut duration = chrono::Duration::zero();
+    let mut number = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let n = number
+            .parse::<i64>()
+            .map_err(|_| anyhow!("Invalid duration \"{s}\""))?;
+
+        duration += match c {
+            'h' => chrono::Duration::hours(n),
+            'm' => chrono::Duration::minutes(n),
+            's' => chrono::Duration::seconds(n),
+            _ => return Err(anyhow!("Invalid duration \"{s}\"")),
+        };
+
+        number.clear();
+    }
+
+    if !number.is_empty() || s.is_empty() {
+        return Err(anyhow!(
+            "Invalid duration \"{s}\", missing unit (h, m or s)"
+        ));
+    }
+
+    Ok(duration)
+}
+
+fn parse_condition(token: &str) -> Result<Condition, anyhow::Error> {
+    let Some(pos) = token.find(['=', '!', '<', '>', '~']) else {
+        return Err(anyhow!("Invalid condition \"{token}\""));
+    };
+
+    let (field, rest) = token.split_at(pos);
+    let (op, value) = match rest {
+        r if r.starts_with("!=") => (Op::Ne, &r[2..]),
+        r if r.starts_with("<=") => (Op::Le, &r[2..]),
+        r if r.starts_with(">=") => (Op::Ge, &r[2..]),
+        r if r.starts_with('=') => (Op::Eq, &r[1..]),
+        r if r.starts_with('<') => (Op::Lt, &r[1..]),
+        r if r.starts_with('>') => (Op::Gt, &r[1..]),
+        r if r.starts_with('~') => (Op::Match, &r[1..]),
+        _ => return Err(anyhow!("Invalid condition \"{token}\"")),
+    };
+
+    let value = value.trim_matches('"');
+    let is_equality = op == Op::Eq || op == Op::Ne;
+    let text_field = match field.to_lowercase().as_str() {
+        "description" => Some(TextField::Description),
+        "project" => Some(TextField::Project),
+        "tag" => Some(TextField::Tag),
+        _ => None,
+    };
+
+    if let Some(text_field) = text_field {
+        let patterns = match op {
+            Op::Match => vec![Regex::new(&format!("(?i){value}"))?],
+            _ if is_equality => value
+                .split(',')
+                .map(glob_to_regex)
+                .collect::<Result<Vec<Regex>, anyhow::Error>>()?,
+            _ => return Err(anyhow!("Cannot compare {field} in \"{token}\"")),
+        };
+
+        return Ok(Condition::Text(text_field, op, patterns));
+    }
+
+    match field.to_lowercase().as_str() {
+        "duration" if op != Op::Match => Ok(Condition::Duration(op, parse_duration(value)?)),
+        "weekday" if is_equality => Ok(Condition::Weekday(
+            op,
+            value
+                .split(',')
+                .map(|d| {
+                    chrono::Weekday::from_str(d).map_err(|_| anyhow!("Invalid weekday \"{d}\""))
+                })
+                .collect::<Result<Vec<chrono::Weekday>, anyhow::Error>>()?,
+        )),
+        "time" if op != Op::Match => Ok(Condition::Time(
+            op,
+            chrono::NaiveTime::parse_from_str(value, "%H:%M")
+                .or_else(|_| chrono::NaiveTime::parse_from_str(value, "%H:%M:%S"))
+                .map_err(|_| anyhow!("Invalid time \"{value}\""))?,
+        )),
+        "duration" | "weekday" | "time" => Err(anyhow!("Cannot compare {field} in \"{token}\"")),
+        _ => Err(anyhow!("Unknown field \"{field}\"")),
+    }
+}
+
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = vec![];
+    let mut token = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                token.push(c);
+            }
+            c if in_quotes => token.push(c),
+            '(' | ')' => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+                tokens.push(c.to_string());
+            }
+            c if c.is_whitespace() => {
+                if !token.is_empty() {
+                    tokens.push(std::mem::take(&mut token));
+                }
+            }
+            c => token.push(c),
+        }
+    }
+
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<String> {
+        self.tokens.get(self.pos).map(|t| t.to_lowercase())
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, anyhow::Error> {
+        let mut expr = self.parse_and()?;
+        while self.peek().as_deref() == Some("or") {
+            self.pos += 1;
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+
+        Ok(expr)
+    }
+
+    // "and" is optional, so "project=work tag=meeting" is the same as "project=work and tag=meeting"
+    fn parse_and(&mut self) -> Result<Expr, anyhow::Error> {
+        let mut expr = self.parse_not()?;
+        loop {
+            match self.peek().as_deref() {
+                None | Some("or") | Some(")") => return Ok(expr),
+                Some("and") => self.pos += 1,
+                _ => {}
+            }
+
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, anyhow::Error> {
+        if self.peek().as_deref() == Some("not") {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(anyhow!("Unexpected end of filter"));
+        };
+
+        self.pos += 1;
+        if token != "(" {
+            return Ok(Expr::Condition(parse_condition(&token)?));
+        }
+
+        let expr = self.parse_or()?;
+        if self.peek().as_deref() != Some(")") {
+            return Err(anyhow!("Missing closing parenthesis"));
+        }
+
+        self.pos += 1;
+        Ok(expr)
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s),
+            pos: 0,
+        };
+
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(anyhow!("Unexpected \"{token}\" in filter"));
+        }
+
+        Ok(Filter(expr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(project: &str, tags: &[&str], start: &str, stop: &str) -> TimeEvent {
+        TimeEvent {
+            description: "Fixed the login bug".to_string(),
+            project: project.to_string(),
+            start: start.parse().unwrap(),
+            stop: Some(stop.parse().unwrap()),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..TimeEvent::default()
+        }
+    }
+
+    fn matches(filter: &str, event: &TimeEvent) -> bool {
+        Filter::from_str(filter).unwrap().matches(event)
+    }
+
+    #[test]
+    fn test_text_conditions() {
+        // 2025-09-08 is a Monday
+        let e = event(
+            "app-tt",
+            &["rust", "cli"],
+            "2025-09-08T09:00:00",
+            "2025-09-08T11:30:00",
+        );
+        assert!(matches("project=app-tt", &e));
+        assert!(matches("project=work,app-tt", &e));
+        assert!(matches("project=app-*", &e));
+        assert!(!matches("project=app", &e));
+        assert!(matches("project!=work", &e));
+        assert!(matches("tag=cli", &e));
+        assert!(!matches("tag!=cli", &e));
+        assert!(matches("description~\"login (bug|issue)\"", &e));
+        assert!(matches("description~LOGIN", &e));
+        assert!(!matches("description~logout", &e));
+    }
+
+    #[test]
+    fn test_other_conditions() {
+        let e = event(
+            "app-tt",
+            &["rust"],
+            "2025-09-08T09:00:00",
+            "2025-09-08T11:30:00",
+        );
+        assert!(matches("duration>2h", &e));
+        assert!(matches("duration>=2h30m", &e));
+        assert!(!matches("duration<150m", &e));
+        assert!(matches("weekday=mon,tue", &e));
+        assert!(matches("weekday!=sat,sun", &e));
+        assert!(matches("time>=09:00", &e));
+        assert!(!matches("time<09:00", &e));
+    }
+
+    #[test]
+    fn test_boolean_logic() {
+        let e = event(
+            "work",
+            &["meeting"],
+            "2025-09-08T09:00:00",
+            "2025-09-08T10:00:00",
+        );
+        assert!(matches("project=work tag=meeting", &e));
+        assert!(matches("project=home or tag=meeting", &e));
+        assert!(!matches("project=work and not tag=meeting", &e));
+        assert!(matches("not (project=home or tag=rust)", &e));
+        assert!(matches(
+            "NOT project=home AND (tag=rust OR duration<=1h)",
+            &e
+        ));
+    }
+
+    #[test]
+    fn test_invalid_filter() {
+        for f in [
+            "",
+            "project",
+            "foo=bar",
+            "duration>2",
+            "duration~2h",
+            "weekday<mon",
+            "weekday=funday",
+            "time>25:00",
+            "project<work",
+            "(project=work",
+            "project=work)",
+            "description~(",
+        ] {
+            assert!(Filter::from_str(f).is_err(), "{f}");
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::config::project_config;
+use crate::event::TimeEvent;
+use crate::utils::default_project;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// Finds the closest parent directory with .git, which is a directory in a
+/// repository and a file with "gitdir: ..." in a worktree or submodule
+pub fn git_root(dir: &Path) -> Option<&Path> {
+    dir.ancestors().find(|d| d.join(".git").exists())
+}
+
+fn git_dir(dir: &Path) -> Option<PathBuf> {
+    let root = git_root(dir)?;
+    let git = root.join(".git");
+    if git.is_dir() {
+        return Some(git);
+    }
+
+    let content = std::fs::read_to_string(&git).ok()?;
+    let path = content.strip_prefix("gitdir:")?.trim();
+    Some(root.join(path))
+}
+
+/// Reads the current branch from .git/HEAD, without running git
+pub fn current_branch(dir: &Path) -> Option<String> {
+    let head = std::fs::read_to_string(git_dir(dir)?.join("HEAD")).ok()?;
+    head.trim()
+        .strip_prefix("ref: refs/heads/")
+        .map(|branch| branch.to_string())
+}
+
+pub fn issue_keys(branch: &str, pattern: &str) -> Result<Vec<String>, anyhow::Error> {
+    let re = Regex::new(pattern)?;
+    Ok(re
+        .find_iter(branch)
+        .map(|m| m.as_str().to_string())
+        .collect())
+}
+
+/// Adds the current branch and the issue keys in the branch name to an event,
+/// if the event is started for the project in the current directory
+pub fn add_branch_info(event: &mut TimeEvent) -> Result<(), anyhow::Error> {
+    let cwd = std::env::current_dir()?;
+    if event.project != default_project() {
+        return Ok(());
+    }
+
+    let Some(branch) = current_branch(&cwd) else {
+        return Ok(());
+    };
+
+    let config = project_config(&cwd, &event.project)?;
+    if config.branch_tag.unwrap_or(true) {
+        event.add_tags(vec![&branch]);
+    }
+
+    let pattern = config
+        .issue_pattern
+        .as_deref()
+        .unwrap_or("[A-Z][A-Z0-9]+-[0-9]+");
+    let keys = issue_keys(&branch, pattern)?;
+    match config.issue_keys.as_deref().unwrap_or("tag") {
+        "tag" => event.add_tags(keys),
+        "description" if event.description.is_empty() => event.description = keys.join(" "),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_branch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("app-tt");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("src")).unwrap();
+        assert_eq!(current_branch(&repo), None);
+
+        std::fs::write(
+            repo.join(".git/HEAD"),
+            "ref: refs/heads/feature/ABC-123-login\n",
+        )
+        .unwrap();
+        let branch = current_branch(&repo.join("src")).unwrap();
+        assert_eq!(branch, "feature/ABC-123-login");
+        assert_eq!(
+            issue_keys(&branch, "[A-Z][A-Z0-9]+-[0-9]+").unwrap(),
+            vec!["ABC-123"]
+        );
+
+        // Detached HEAD
+        std::fs::write(repo.join(".git/HEAD"), "0123456789abcdef\n").unwrap();
+        assert_eq!(current_branch(&repo), None);
+
+        let worktree = tmp.path().join("worktree");
+        std::fs::create_dir_all(repo.join(".git/worktrees/wt")).unwrap();
+        std::fs::create_dir_all(&worktree).unwrap();
+        std::fs::write(
+            worktree.join(".git"),
+            "gitdir: ../app-tt/.git/worktrees/wt\n",
+        )
+        .unwrap();
+        std::fs::write(
+            repo.join(".git/worktrees/wt/HEAD"),
+            "ref: refs/heads/main\n",
+        )
+        .unwrap();
+        assert_eq!(current_branch(&worktree).as_deref(), Some("main"));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::config::config_dir;
+use crate::event::{TimeEvent, path_id};
+use crate::utils::{format_date, hook_timeout};
+use anyhow::anyhow;
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+/// Runs the hooks, such as "on-save", in ~/.config/tt/hooks/ for an event.
+/// The event is already written, so a failing hook is reported, but not fatal.
+pub fn run(names: &[&str], event: &TimeEvent) {
+    for name in names {
+        if let Err(e) = run_hook(name, event, None) {
+            eprintln!("Warning: The {name} hook failed: {e}");
+        }
+    }
+}
+
+/// Runs "on-save" for an event written by `TimeEvent::move_from()`. The old id and
+/// file are available to the hook as TT_OLD_ID and TT_OLD_FILE, if the file was moved.
+pub fn run_moved(event: &TimeEvent, old_path: &Path) {
+    let old_path = Some(old_path).filter(|p| *p != event.path());
+    if let Err(e) = run_hook("on-save", event, old_path) {
+        eprintln!("Warning: The on-save hook failed: {e}");
+    }
+}
+
+/// Runs the hooks for an event that was stopped with `TimeEvent::stop_at()`
+pub fn run_stopped(event: &TimeEvent, saved: bool) {
+    let hook = if saved { "on-save" } else { "on-delete" };
+    run(&["on-stop", hook], event);
+}
+
+fn run_hook(name: &str, event: &TimeEvent, old_path: Option<&Path>) -> Result<(), anyhow::Error> {
+    let path = config_dir().join("hooks").join(name);
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let mut json = event.to_json()?;
+    json["id"] = event.id().into();
+
+    let mut command = std::process::Command::new(&path);
+    if let Some(old_path) = old_path {
+        command
+            .env("TT_OLD_ID", path_id(old_path))
+            .env("TT_OLD_FILE", old_path);
+    }
+
+    let mut child = command
+        .env("TT_HOOK", name)
+        .env("TT_ID", event.id())
+        .env("TT_PROJECT", &event.project)
+        .env("TT_START", format_date(&event.start, "full"))
+        .env(
+            "TT_STOP",
+            event
+                .stop
+                .map(|s| format_date(&s, "full"))
+                .unwrap_or_default(),
+        )
+        .env("TT_DURATION", event.duration().num_seconds().to_string())
+        .env("TT_TAGS", event.tags.join(","))
+        .env("TT_DESCRIPTION", &event.description)
+        .env("TT_FILE", event.path())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow!("Unable to run {path:?}: {e}"))?;
+
+    // The hook might exit without reading stdin, so write errors are ignored
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(json.to_string().as_bytes());
+    }
+
+    let timeout = Duration::from_secs(hook_timeout()?);
+    let started = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return if status.success() {
+                Ok(())
+            } else {
+                Err(anyhow!("{path:?} {status}"))
+            };
+        }
+
+        if started.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            return Err(anyhow!(
+                "{path:?} was killed after {} seconds",
+                timeout.as_secs()
+            ));
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -1,9 +1,39 @@
 mod commands;
+mod completion;
+mod config;
 mod event;
+mod filter;
+mod git;
+mod hooks;
 mod styling;
 mod utils;
 
 use clap::{Arg, ArgAction, Command};
+use clap_complete::CompleteEnv;
+
+fn dry_run_arg() -> Arg {
+    Arg::new("dry_run")
+        .help("Only show what would be done")
+        .long("dry-run")
+        .num_args(0)
+        .action(ArgAction::SetTrue)
+}
+
+fn filter_arg() -> Arg {
+    Arg::new("filter")
+        .help("Filter expression, such as 'project=work,home and not tag=meeting'")
+        .short('f')
+        .long("filter")
+        .value_parser(|s: &str| s.parse::<filter::Filter>())
+}
+
+fn json_arg() -> Arg {
+    Arg::new("json")
+        .help("Print the output as JSON")
+        .long("json")
+        .num_args(0)
+        .action(ArgAction::SetTrue)
+}
 
 fn quiet_arg() -> Arg {
     Arg::new("quiet")
@@ -13,25 +43,66 @@ fn quiet_arg() -> Arg {
         .action(ArgAction::SetTrue)
 }
 
-fn main() {
-    let matches = Command::new("tt")
+fn cli() -> Command {
+    let cmd = Command::new("tt")
         .version(env!("CARGO_PKG_VERSION"))
         .about(include_str!("about.txt"))
         .author("Jan Henning Thorsen <jan.henning@thorsenlabs.com>")
         .subcommand(commands::start::command())
         .subcommand(commands::stop::command())
+        .subcommand(commands::switch::command())
+        .subcommand(commands::pause::command())
+        .subcommand(commands::unpause::command())
+        .subcommand(commands::ping::command())
         .subcommand(commands::status::command())
         .subcommand(commands::report::command())
         .subcommand(commands::register::command())
+        .subcommand(commands::search::command())
         .subcommand(commands::edit::command())
-        .get_matches();
+        .subcommand(commands::amend::command())
+        .subcommand(commands::split::command())
+        .subcommand(commands::merge::command())
+        .subcommand(commands::delete::command())
+        .subcommand(commands::log::command())
+        .subcommand(commands::show::command())
+        .subcommand(commands::fsck::command())
+        .subcommand(commands::man::command())
+        .subcommand(commands::project::command())
+        .subcommand(commands::projects::command())
+        .subcommand(commands::tag::command())
+        .subcommand(commands::tags::command());
+
+    completion::add_candidates("", cmd)
+}
+
+fn main() {
+    CompleteEnv::with_factory(cli).complete();
+
+    let matches = cli().get_matches();
 
     let exit_code = match matches.subcommand() {
         Some(("start", args)) => commands::start::run(args),
         Some(("stop", args)) => commands::stop::run(args),
+        Some(("switch", args)) => commands::switch::run(args),
+        Some(("pause", args)) => commands::pause::run(args),
+        Some(("unpause", args)) => commands::unpause::run(args),
+        Some(("ping", args)) => commands::ping::run(args),
         Some(("report", args)) => commands::report::run(args),
         Some(("register", args)) => commands::register::run(args),
+        Some(("search", args)) => commands::search::run(args),
         Some(("edit", args)) => commands::edit::run(args),
+        Some(("amend", args)) => commands::amend::run(args),
+        Some(("split", args)) => commands::split::run(args),
+        Some(("merge", args)) => commands::merge::run(args),
+        Some(("delete", args)) => commands::delete::run(args),
+        Some(("log", args)) => commands::log::run(args),
+        Some(("show", args)) => commands::show::run(args),
+        Some(("fsck", args)) => commands::fsck::run(args),
+        Some(("man", args)) => commands::man::run(args),
+        Some(("project", args)) => commands::project::run(args),
+        Some(("projects", args)) => commands::projects::run(args),
+        Some(("tag", args)) => commands::tag::run(args),
+        Some(("tags", args)) => commands::tags::run(args),
         _ => commands::status::run(&matches),
     };
 
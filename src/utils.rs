@@ -1,13 +1,27 @@
+use crate::config::detect_project;
 use anyhow::anyhow;
+use std::sync::OnceLock;
 
+/// Detects the project for the current directory, once per process
 pub fn default_project() -> String {
-    if let Ok(from_cwd) = std::env::current_dir()
-        && let Some(name) = from_cwd.file_name()
-    {
-        return name.to_string_lossy().to_string();
-    }
-
-    "default".to_string()
+    static PROJECT: OnceLock<String> = OnceLock::new();
+    PROJECT
+        .get_or_init(|| {
+            if let Ok(from_cwd) = std::env::current_dir() {
+                match detect_project(&from_cwd) {
+                    Ok(Some(project)) => return project,
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Warning: {e}"),
+                }
+
+                if let Some(name) = from_cwd.file_name() {
+                    return name.to_string_lossy().to_string();
+                }
+            }
+
+            "default".to_string()
+        })
+        .clone()
 }
 
 pub fn format_date(d: &chrono::NaiveDateTime, format: &'static str) -> String {
@@ -25,12 +39,55 @@ pub fn format_duration(duration: &chrono::Duration) -> String {
     format!("{}h {:02}m", hours, minutes)
 }
 
+pub fn hook_timeout() -> Result<u64, anyhow::Error> {
+    Ok(std::env::var("TT_HOOK_TIMEOUT")
+        .unwrap_or_else(|_| "10".to_string())
+        .parse()?)
+}
+
+pub fn idle_duration() -> Result<i64, anyhow::Error> {
+    Ok(std::env::var("TT_IDLE_DURATION")
+        .unwrap_or_else(|_| "900".to_string())
+        .parse()?)
+}
+
+pub fn max_duration() -> Result<i64, anyhow::Error> {
+    Ok(std::env::var("TT_MAX_DURATION")
+        .unwrap_or_else(|_| "36000".to_string())
+        .parse()?)
+}
+
 pub fn min_duration() -> Result<i64, anyhow::Error> {
     Ok(std::env::var("TT_MIN_DURATION")
         .unwrap_or_else(|_| "300".to_string())
         .parse()?)
 }
 
+/// Same as `to_naive_date_time()`, but defaults to the beginning of time
+/// Reads a time argument, such as "14:30", on the same date as `base`. The default
+/// value is the current time, since `base` can be days ago for a forgotten event.
+pub fn to_time_arg(
+    args: &clap::ArgMatches,
+    id: &str,
+    base: &chrono::NaiveDateTime,
+) -> Result<chrono::NaiveDateTime, anyhow::Error> {
+    if args.value_source(id) == Some(clap::parser::ValueSource::DefaultValue) {
+        let now = chrono::Local::now().naive_local();
+        return to_naive_date_time(Some(&format_date(&now, "hm")), Some(&now));
+    }
+
+    to_naive_date_time(args.get_one::<String>(id), Some(base))
+}
+
+pub fn to_since_date_time(
+    human_date: Option<&String>,
+) -> Result<chrono::NaiveDateTime, anyhow::Error> {
+    match human_date {
+        Some(_) => to_naive_date_time(human_date, None),
+        None => Ok(chrono::NaiveDateTime::MIN),
+    }
+}
+
 pub fn to_naive_date_time(
     human_date: Option<&String>,
     now: Option<&chrono::NaiveDateTime>,
@@ -43,6 +100,10 @@ pub fn to_naive_date_time(
         return Ok(now);
     };
 
+    if human_date.trim().eq_ignore_ascii_case("now") {
+        return Ok(now);
+    }
+
     let mut parts: Vec<String> = human_date
         .trim()
         .replace(" ", "T")